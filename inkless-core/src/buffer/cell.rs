@@ -0,0 +1,112 @@
+use core::ops::{ControlFlow, Deref};
+
+use crate::{
+    grapheme::gph,
+    tag::{Tag, sink::TagSink},
+};
+
+/// The line-level half of a `TagSink`, object safe so the buffer does not need to name the sink's
+/// `Result` type.
+pub(crate) trait LineSink<T: Tag> {
+    fn append(&mut self, grapheme: &gph, tag: T) -> ControlFlow<()>;
+    fn gap(&mut self) -> ControlFlow<()>;
+    fn finalize_line(&mut self) -> ControlFlow<()>;
+}
+
+impl<T: Tag, S: TagSink<T>> LineSink<T> for S {
+    fn append(&mut self, grapheme: &gph, tag: T) -> ControlFlow<()> {
+        TagSink::append(self, grapheme, tag)
+    }
+
+    fn gap(&mut self) -> ControlFlow<()> {
+        TagSink::gap(self)
+    }
+
+    fn finalize_line(&mut self) -> ControlFlow<()> {
+        TagSink::finalize_line(self)
+    }
+}
+
+/// One column of a buffer row.
+///
+/// A grapheme `n` columns wide is stored as a `Grapheme` cell followed by `n - 1` `Continuation`
+/// cells, so every column of a row always says what occupies it.
+#[derive(Debug, Clone, Default)]
+pub enum Cell<T, G> {
+    /// Nothing has been written here; the sink receives a gap.
+    #[default]
+    Empty,
+
+    /// The left-most column of a grapheme.
+    Grapheme { tag: T, grapheme: G },
+
+    /// A column covered by the wide grapheme to its left.
+    Continuation,
+}
+
+impl<T, G: Deref<Target = gph>> Cell<T, G> {
+    /// Returns the grapheme and tag if this cell starts a grapheme.
+    pub fn get(&self) -> Option<(&gph, &T)> {
+        match self {
+            Cell::Grapheme { tag, grapheme } => Some((&**grapheme, tag)),
+            Cell::Empty | Cell::Continuation => None,
+        }
+    }
+}
+
+/// Writes a grapheme spanning `width` columns into `row` at `column`.
+///
+/// Any wide grapheme that the write only partly covers is removed entirely, leaving `Empty` cells in
+/// the columns it no longer occupies. A grapheme with a width of 0 still takes up its column.
+///
+/// Writes that do not fit in `row` are dropped.
+pub(crate) fn write_to_row<T, G>(
+    row: &mut [Cell<T, G>],
+    column: usize,
+    width: usize,
+    tag: T,
+    grapheme: G,
+) {
+    let end = column + width.max(1);
+
+    if end > row.len() {
+        return;
+    }
+
+    // The right half of a grapheme starting to the left is being overwritten.
+    if matches!(row[column], Cell::Continuation) {
+        let lead = row[..column]
+            .iter()
+            .rposition(|cell| !matches!(cell, Cell::Continuation))
+            .unwrap_or(0);
+
+        row[lead..column].fill_with(|| Cell::Empty);
+    }
+
+    // The left half of a grapheme reaching past the end is being overwritten.
+    for cell in row[end..]
+        .iter_mut()
+        .take_while(|cell| matches!(cell, Cell::Continuation))
+    {
+        *cell = Cell::Empty;
+    }
+
+    row[column] = Cell::Grapheme { tag, grapheme };
+    row[column + 1..end].fill_with(|| Cell::Continuation);
+}
+
+/// Passes one row to the sink, followed by `finalize_line`.
+pub(crate) fn dispatch_row<T: Tag, G: Deref<Target = gph>, S: LineSink<T> + ?Sized>(
+    row: impl IntoIterator<Item = Cell<T, G>>,
+    sink: &mut S,
+) -> ControlFlow<()> {
+    for cell in row {
+        match cell {
+            Cell::Empty => sink.gap()?,
+            Cell::Grapheme { tag, grapheme } => sink.append(&grapheme, tag)?,
+            Cell::Continuation => {}
+        }
+    }
+
+    sink.finalize_line()
+}
@@ -0,0 +1,48 @@
+use crate::grapheme::{char::CharGrapheme, gph};
+
+/// What a canvas writes in place of a control character, so untrusted text cannot move the
+/// terminal's cursor, ring its bell or start an escape sequence.
+///
+/// Applies to every C0 control, DEL and every C1 control, except `\t`, which is expanded to the
+/// canvas's tab stops.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ControlPolicy {
+    /// Drops control characters entirely.
+    Strip,
+    /// Writes U+FFFD REPLACEMENT CHARACTER in place of each control character.
+    #[default]
+    Replace,
+    /// Writes the matching symbol from the Control Pictures block, such as `␛` for ESC. C1
+    /// controls have no picture and are replaced with U+FFFD.
+    ControlPictures,
+}
+
+impl ControlPolicy {
+    /// Returns what to write in place of `control`, or `None` if it should be dropped.
+    ///
+    /// `control` is expected to be a grapheme for which `is_control` returned `true`.
+    pub fn replacement(&self, control: &gph) -> Option<CharGrapheme> {
+        match self {
+            ControlPolicy::Strip => None,
+            ControlPolicy::Replace => Some(CharGrapheme::from(char::REPLACEMENT_CHARACTER)),
+            ControlPolicy::ControlPictures => Some(CharGrapheme::from(
+                match control.as_str() {
+                    "\r\n" => Some('\u{2424}'),
+                    s => s.chars().next().and_then(|c| match c {
+                        '\0'..='\x1f' => char::from_u32(0x2400 + c as u32),
+                        '\x7f' => Some('\u{2421}'),
+                        _ => None,
+                    }),
+                }
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
+            )),
+        }
+    }
+}
+
+/// Returns whether `grapheme` contains a C0 control, DEL or a C1 control.
+///
+/// Control characters always form a grapheme of their own, except for `\r\n`.
+pub fn is_control(grapheme: &gph) -> bool {
+    grapheme.as_str().chars().any(char::is_control)
+}
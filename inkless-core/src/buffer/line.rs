@@ -0,0 +1,49 @@
+use core::marker::PhantomData;
+
+use crate::{buffer::RenderBuffer, grapheme::gph, render_position::RenderPosition, tag::Tag};
+
+/// An iterator over the cells of one line of a `RenderBuffer`, from column 0 to `line_len`.
+///
+/// Yields `None` for empty cells and for the cells covered by the right half of a wide grapheme.
+pub struct LineCells<'a, T: Tag, B: RenderBuffer<T> + ?Sized> {
+    buffer: &'a B,
+    line: usize,
+    column: usize,
+    len: usize,
+    _tag: PhantomData<T>,
+}
+
+impl<'a, T: Tag, B: RenderBuffer<T> + ?Sized> LineCells<'a, T, B> {
+    pub fn new(buffer: &'a B, line: usize) -> Self {
+        Self {
+            buffer,
+            line,
+            column: 0,
+            len: buffer.line_len(line),
+            _tag: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: Tag + 'a, B: RenderBuffer<T> + ?Sized> Iterator for LineCells<'a, T, B> {
+    type Item = Option<(&'a gph, &'a T)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.column >= self.len {
+            return None;
+        }
+
+        let cell = self
+            .buffer
+            .get_cell(RenderPosition::new(self.line, self.column));
+        self.column += 1;
+
+        Some(cell)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.len - self.column;
+
+        (remaining, Some(remaining))
+    }
+}
@@ -1,8 +1,21 @@
+pub mod cell;
+pub mod control;
+pub mod line;
+pub mod null;
 pub mod r#static;
+pub mod streaming;
+
+#[cfg(feature = "alloc")]
+pub mod unbounded;
+#[cfg(feature = "alloc")]
+pub mod vec;
+
+use core::ops::Range;
 
 use crate::{
-    canvas::AmbiguityPolicy,
-    grapheme::gph,
+    buffer::{control::ControlPolicy, line::LineCells},
+    diagnostics::{Diagnostic, Diagnostics},
+    grapheme::{gph, width::WidthProvider},
     render_position::RenderPosition,
     renderable::Renderable,
     tag::{Tag, sink::TagSink},
@@ -26,14 +39,96 @@ pub trait RenderBuffer<T: Tag> {
     /// Returns the width of the RenderBuffer (if it has one). This is in the same units as `Grapheme::width` and `Grapheme::width_cjk`
     fn width(&self) -> Option<usize>;
 
-    fn ambiguity_policy(&self) -> AmbiguityPolicy;
+    /// Returns the provider this buffer measures graphemes with.
+    fn width_provider(&self) -> &dyn WidthProvider;
+
+    /// Returns what canvases writing to this buffer put in place of control characters.
+    fn control_policy(&self) -> ControlPolicy {
+        ControlPolicy::default()
+    }
+
+    /// Declares that every line above `line` is finished and will never be written again.
+    ///
+    /// Buffers that stream their output may hand those lines to the sink immediately. Buffers that
+    /// keep every line until the render completes are free to ignore this.
+    fn set_line_watermark(&mut self, _line: usize) {}
+
+    /// Records a warning reported while rendering into this buffer.
+    ///
+    /// Buffers that do not collect diagnostics are free to ignore this.
+    fn report(&mut self, _diagnostic: Diagnostic) {}
+
+    /// Returns the grapheme and tag of the cell at `position`.
+    ///
+    /// Returns `None` for empty cells, for the cells covered by the right half of a wide grapheme,
+    /// and for lines outside `readable_lines`.
+    fn get_cell(&self, position: RenderPosition) -> Option<(&gph, &T)>;
+
+    /// Returns the range of lines this buffer currently holds and can read back through `get_cell`.
+    fn readable_lines(&self) -> Range<usize>;
+
+    /// Returns the number of columns that can be read from `line`.
+    fn line_len(&self, _line: usize) -> usize {
+        self.width().unwrap_or(0)
+    }
+
+    /// Returns an iterator over the cells of `line`.
+    fn line_cells(&self, line: usize) -> LineCells<'_, T, Self>
+    where
+        Self: Sized,
+    {
+        LineCells::new(self, line)
+    }
+}
+
+/// The outcome of `RenderDispatcher::render_viewport`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ViewportResult<R> {
+    /// What the sink returned from `finalize`.
+    pub result: R,
+
+    /// The number of lines the renderable occupies in full, regardless of the requested range.
+    pub total_height: usize,
 }
 
 pub trait RenderDispatcher<T: Tag, R: Renderable<T>> {
+    /// Renders with the default `ControlPolicy`.
     fn render<S: TagSink<T>>(
         sink: S,
         renderable: R,
         width: usize,
-        ambiguity_policy: AmbiguityPolicy,
-    ) -> S::Result;
+        width_provider: &dyn WidthProvider,
+    ) -> S::Result {
+        Self::render_with_diagnostics(
+            sink,
+            renderable,
+            width,
+            width_provider,
+            ControlPolicy::default(),
+        )
+        .0
+    }
+
+    /// Renders like `render`, writing control characters as `control_policy` says and also
+    /// returning the warnings reported while rendering.
+    fn render_with_diagnostics<S: TagSink<T>>(
+        sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        control_policy: ControlPolicy,
+    ) -> (S::Result, Diagnostics);
+
+    /// Renders only the lines in `lines`, passing them to the sink as if they were the whole output.
+    ///
+    /// Lines past the end of the renderable are not emitted, so the sink may receive fewer lines
+    /// than requested.
+    fn render_viewport<S: TagSink<T>>(
+        sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        lines: Range<usize>,
+        control_policy: ControlPolicy,
+    ) -> ViewportResult<S::Result>;
 }
@@ -0,0 +1,121 @@
+use core::{marker::PhantomData, mem, ops::Range};
+
+use crate::{
+    buffer::{RenderBuffer, control::ControlPolicy},
+    diagnostics::{Diagnostic, Diagnostics},
+    grapheme::{gph, width::WidthProvider},
+    render_position::{RenderPosition, Size},
+    tag::Tag,
+};
+
+/// A render buffer that stores nothing and only tracks the area written to.
+///
+/// This is what `measure` renders into; it is useful on its own to size a renderable without
+/// producing any output.
+#[derive(Debug)]
+pub struct NullRenderBuffer<'w, T: Tag> {
+    width: Option<usize>,
+    lowest_written_line: Option<usize>,
+    max_column: usize,
+    cells_set: usize,
+    width_provider: &'w dyn WidthProvider,
+    control_policy: ControlPolicy,
+    diagnostics: Diagnostics,
+    _tag: PhantomData<T>,
+}
+
+impl<'w, T: Tag> NullRenderBuffer<'w, T> {
+    /// Creates a buffer with the given width, or with no width limit if `width` is `None`.
+    pub fn new(width: Option<usize>, width_provider: &'w dyn WidthProvider) -> Self {
+        Self {
+            width,
+            lowest_written_line: None,
+            max_column: 0,
+            cells_set: 0,
+            width_provider,
+            control_policy: ControlPolicy::default(),
+            diagnostics: Diagnostics::default(),
+            _tag: PhantomData,
+        }
+    }
+
+    /// Sets what canvases writing to this buffer put in place of control characters.
+    pub fn with_control_policy(mut self, control_policy: ControlPolicy) -> Self {
+        self.control_policy = control_policy;
+        self
+    }
+
+    /// Returns the diagnostics reported so far.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the diagnostics reported so far.
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        mem::take(&mut self.diagnostics)
+    }
+
+    /// Returns the bounding box of every cell written so far, measured from the origin.
+    pub fn size(&self) -> Size {
+        Size::new(
+            self.lowest_written_line.map_or(0, |line| line + 1),
+            self.max_column,
+        )
+    }
+
+    /// Returns how many cells were successfully set.
+    pub fn cells_set(&self) -> usize {
+        self.cells_set
+    }
+}
+
+impl<'w, T: Tag> RenderBuffer<T> for NullRenderBuffer<'w, T> {
+    fn can_set_cell(&self, position: RenderPosition, c: &gph) -> bool {
+        self.width
+            .is_none_or(|width| position.column() + c.width(self.width_provider) <= width)
+    }
+
+    fn set_cell(&mut self, position: RenderPosition, c: &gph, _tag: T) -> bool {
+        if !self.can_set_cell(position, c) {
+            return false;
+        }
+
+        if self
+            .lowest_written_line
+            .is_none_or(|line| position.line() > line)
+        {
+            self.lowest_written_line = Some(position.line());
+        }
+
+        self.max_column = self
+            .max_column
+            .max(position.column() + c.width(self.width_provider));
+        self.cells_set += 1;
+
+        true
+    }
+
+    fn width(&self) -> Option<usize> {
+        self.width
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.width_provider
+    }
+
+    fn control_policy(&self) -> ControlPolicy {
+        self.control_policy
+    }
+
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn get_cell(&self, _position: RenderPosition) -> Option<(&gph, &T)> {
+        None
+    }
+
+    fn readable_lines(&self) -> Range<usize> {
+        0..0
+    }
+}
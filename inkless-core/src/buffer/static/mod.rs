@@ -1,36 +1,71 @@
-use core::fmt::Debug;
+use core::{
+    fmt::Debug,
+    mem,
+    ops::{ControlFlow, Range},
+};
 
 use crate::{
-    buffer::{RenderBuffer, RenderDispatcher},
-    canvas::{AmbiguityPolicy, ext::RenderBufferCanvasExt},
-    grapheme::{gph, r#static::StaticGrapheme},
+    buffer::{
+        RenderBuffer, RenderDispatcher, ViewportResult,
+        cell::{Cell, dispatch_row, write_to_row},
+        control::ControlPolicy,
+    },
+    canvas::ext::RenderBufferCanvasExt,
+    diagnostics::{Diagnostic, DiagnosticKind, Diagnostics},
+    grapheme::{gph, r#static::StaticGrapheme, width::WidthProvider},
     render_position::RenderPosition,
     renderable::Renderable,
     tag::{Tag, sink::TagSink},
 };
 
 #[derive(Debug)]
-pub struct StaticRenderBuffer<T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize = 7> {
-    cells: [Option<(T, StaticGrapheme<GRAPHEME_WIDTH>)>; CELLS],
+pub struct StaticRenderBuffer<'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize = 7> {
+    cells: [Cell<T, StaticGrapheme<GRAPHEME_WIDTH>>; CELLS],
     width: usize,
     offset: usize,
-    lowest_written_line: usize,
-    ambiguity_policy: AmbiguityPolicy,
+    lowest_written_line: Option<usize>,
+    width_provider: &'w dyn WidthProvider,
+    control_policy: ControlPolicy,
+    diagnostics: Diagnostics,
 }
 
-impl<T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize>
-    StaticRenderBuffer<T, CELLS, GRAPHEME_WIDTH>
+impl<'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize>
+    StaticRenderBuffer<'w, T, CELLS, GRAPHEME_WIDTH>
 {
-    pub fn new(width: usize, offset: usize, ambiguity_policy: AmbiguityPolicy) -> Self {
+    /// Panics if `width` is 0 or larger than `CELLS`, since the buffer must hold at least one line.
+    pub fn new(width: usize, offset: usize, width_provider: &'w dyn WidthProvider) -> Self {
+        assert!(
+            width > 0 && width <= CELLS,
+            "Expected a width between 1 and {CELLS}, got {width}"
+        );
+
         Self {
-            cells: core::array::from_fn(|_| None),
+            cells: core::array::from_fn(|_| Cell::Empty),
             width,
             offset,
-            lowest_written_line: core::usize::MIN,
-            ambiguity_policy,
+            lowest_written_line: None,
+            width_provider,
+            control_policy: ControlPolicy::default(),
+            diagnostics: Diagnostics::default(),
         }
     }
 
+    /// Sets what canvases writing to this buffer put in place of control characters.
+    pub fn with_control_policy(mut self, control_policy: ControlPolicy) -> Self {
+        self.control_policy = control_policy;
+        self
+    }
+
+    /// Returns the diagnostics reported so far.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the diagnostics reported so far.
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        mem::take(&mut self.diagnostics)
+    }
+
     fn height(&self) -> usize {
         CELLS / self.width
     }
@@ -46,15 +81,32 @@ impl<T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize>
     }
 
     pub fn is_empty(&self) -> bool {
-        self.offset > self.lowest_written_line
+        self.offset > self.lowest_written_line.unwrap_or(0)
+    }
+
+    /// Returns the number of lines the renderable has written to, including those outside this
+    /// buffer's window.
+    pub fn total_height(&self) -> usize {
+        self.lowest_written_line.map_or(0, |line| line + 1)
+    }
+
+    fn dispatch_lines<S: TagSink<T>>(self, sink: &mut S, lines: usize) -> ControlFlow<()> {
+        let width = self.width;
+        let mut cells = self.cells.into_iter();
+
+        for _ in 0..lines {
+            dispatch_row(cells.by_ref().take(width), sink)?;
+        }
+
+        ControlFlow::Continue(())
     }
 }
 
-impl<T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize> RenderBuffer<T>
-    for StaticRenderBuffer<T, CELLS, GRAPHEME_WIDTH>
+impl<'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize> RenderBuffer<T>
+    for StaticRenderBuffer<'w, T, CELLS, GRAPHEME_WIDTH>
 {
     fn can_set_cell(&self, position: RenderPosition, c: &gph) -> bool {
-        position.column() + c.width(self.ambiguity_policy()) <= self.width
+        position.column() + c.width(self.width_provider) <= self.width
     }
 
     fn set_cell(&mut self, position: RenderPosition, c: &gph, tag: T) -> bool {
@@ -62,12 +114,31 @@ impl<T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize> RenderBuffer<T>
             return false;
         }
 
-        if position.line() > self.lowest_written_line {
-            self.lowest_written_line = position.line();
+        if self
+            .lowest_written_line
+            .is_none_or(|line| position.line() > line)
+        {
+            self.lowest_written_line = Some(position.line());
         }
 
         if let Some(idx) = self.index_of(position) {
-            self.cells[idx] = Some((tag, StaticGrapheme::from_single_grapheme(c)));
+            let row_start = idx - position.column();
+            let grapheme = StaticGrapheme::from_single_grapheme(c);
+
+            if grapheme.did_overflow() {
+                self.report(Diagnostic::new(
+                    DiagnosticKind::UnrepresentableGrapheme,
+                    position,
+                ));
+            }
+
+            write_to_row(
+                &mut self.cells[row_start..row_start + self.width],
+                position.column(),
+                c.width(self.width_provider),
+                tag,
+                grapheme,
+            );
         }
 
         true
@@ -77,73 +148,117 @@ impl<T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize> RenderBuffer<T>
         Some(self.width)
     }
 
-    fn ambiguity_policy(&self) -> AmbiguityPolicy {
-        self.ambiguity_policy
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.width_provider
+    }
+
+    fn control_policy(&self) -> ControlPolicy {
+        self.control_policy
+    }
+
+    /// Diagnostics are only kept for lines in this buffer's window, so that rendering the same
+    /// renderable once per window reports each of them once.
+    fn report(&mut self, diagnostic: Diagnostic) {
+        if self.readable_lines().contains(&diagnostic.position.line()) {
+            self.diagnostics.push(diagnostic);
+        }
+    }
+
+    fn get_cell(&self, position: RenderPosition) -> Option<(&gph, &T)> {
+        self.index_of(position)
+            .and_then(|index| self.cells[index].get())
+    }
+
+    fn readable_lines(&self) -> Range<usize> {
+        self.offset..self.offset + self.height()
     }
 }
 
-impl<'a, T: Tag + 'a, const CELLS: usize, const GRAPHEME_WIDTH: usize, R: Renderable<T>>
-    RenderDispatcher<T, R> for StaticRenderBuffer<T, CELLS, GRAPHEME_WIDTH>
+impl<'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize, R: Renderable<T>>
+    RenderDispatcher<T, R> for StaticRenderBuffer<'w, T, CELLS, GRAPHEME_WIDTH>
 {
-    fn render<S: TagSink<T>>(
+    fn render_with_diagnostics<S: TagSink<T>>(
         mut sink: S,
         renderable: R,
         width: usize,
-        ambiguity_policy: AmbiguityPolicy,
-    ) -> S::Result {
+        width_provider: &dyn WidthProvider,
+        control_policy: ControlPolicy,
+    ) -> (S::Result, Diagnostics) {
         let mut offset = 0;
+        let mut diagnostics = Diagnostics::default();
 
         loop {
-            let mut buffer = Self::new(width, offset, ambiguity_policy);
+            let mut buffer =
+                StaticRenderBuffer::<T, CELLS, GRAPHEME_WIDTH>::new(width, offset, width_provider)
+                    .with_control_policy(control_policy);
             let mut canvas = buffer.canvas_at(RenderPosition::zero());
+            let result = renderable.render_into(&mut canvas);
 
-            if let Err(e) = renderable.render_into(&mut canvas) {
-                return S::Result::from(e);
+            diagnostics.append(buffer.take_diagnostics());
+
+            if let Err(e) = result {
+                return (S::Result::from(e), diagnostics);
             }
 
             if buffer.is_empty() {
                 break;
             }
 
-            let count = buffer.width * buffer.height();
-            let width = buffer.width;
             let height = buffer.height();
-            let mut skip_count = 0;
 
-            for (index, value) in buffer.cells.into_iter().enumerate() {
-                if skip_count > 0 {
-                    skip_count -= 1;
-                    continue;
-                }
+            if buffer.dispatch_lines(&mut sink, height).is_break() {
+                break;
+            }
 
-                if index >= count {
-                    break;
-                }
+            offset += height;
+        }
 
-                let result = if let Some((tag, grapheme)) = value {
-                    skip_count = grapheme.width(ambiguity_policy) - 1;
+        (sink.finalize(), diagnostics)
+    }
+
+    fn render_viewport<S: TagSink<T>>(
+        mut sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        lines: Range<usize>,
+        control_policy: ControlPolicy,
+    ) -> ViewportResult<S::Result> {
+        let mut offset = lines.start;
 
-                    sink.append(&*grapheme, tag)
-                } else {
-                    sink.gap()
+        loop {
+            let mut buffer =
+                StaticRenderBuffer::<T, CELLS, GRAPHEME_WIDTH>::new(width, offset, width_provider)
+                    .with_control_policy(control_policy);
+            let mut canvas = buffer.canvas_at(RenderPosition::zero());
+
+            if let Err(e) = renderable.render_into(&mut canvas) {
+                return ViewportResult {
+                    result: S::Result::from(e),
+                    total_height: buffer.total_height(),
+                };
+            }
+
+            let total_height = buffer.total_height();
+            let end = lines.end.min(total_height);
+
+            if offset >= end {
+                return ViewportResult {
+                    result: sink.finalize(),
+                    total_height,
                 };
+            }
+
+            let count = buffer.height().min(end - offset);
 
-                match result {
-                    core::ops::ControlFlow::Break(_) => break,
-                    core::ops::ControlFlow::Continue(_) => {}
-                }
-
-                if index % width == width - 1 {
-                    match sink.finalize_line() {
-                        core::ops::ControlFlow::Break(_) => break,
-                        core::ops::ControlFlow::Continue(_) => {}
-                    }
-                }
+            if buffer.dispatch_lines(&mut sink, count).is_break() {
+                return ViewportResult {
+                    result: sink.finalize(),
+                    total_height,
+                };
             }
 
-            offset += height;
+            offset += count;
         }
-
-        sink.finalize()
     }
 }
@@ -0,0 +1,301 @@
+use core::{mem, ops::Range};
+
+use crate::{
+    buffer::{
+        RenderBuffer, RenderDispatcher, ViewportResult,
+        cell::{Cell, LineSink, dispatch_row, write_to_row},
+        control::ControlPolicy,
+    },
+    canvas::ext::RenderBufferCanvasExt,
+    diagnostics::{Diagnostic, DiagnosticKind, Diagnostics},
+    grapheme::{gph, r#static::StaticGrapheme, width::WidthProvider},
+    render_position::RenderPosition,
+    renderable::Renderable,
+    tag::{Tag, sink::TagSink},
+};
+
+/// A fixed-size render buffer that pushes lines to its sink as soon as they are committed.
+///
+/// The buffer holds a rolling window of `CELLS / width` lines. Lines above the watermark set
+/// through `Canvas::set_line_watermark` are flushed immediately; if a renderable writes further
+/// down than the window can hold, the oldest lines are flushed early to make room. Writes to a line
+/// that has already been flushed are discarded.
+pub struct StreamingRenderBuffer<
+    's,
+    'w,
+    T: Tag,
+    const CELLS: usize,
+    const GRAPHEME_WIDTH: usize = 7,
+> {
+    cells: [Cell<T, StaticGrapheme<GRAPHEME_WIDTH>>; CELLS],
+    width: usize,
+    flushed: usize,
+    end: usize,
+    watermark: usize,
+    lowest_written_line: Option<usize>,
+    width_provider: &'w dyn WidthProvider,
+    control_policy: ControlPolicy,
+    diagnostics: Diagnostics,
+    sink: &'s mut dyn LineSink<T>,
+    halted: bool,
+}
+
+impl<'s, 'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize>
+    StreamingRenderBuffer<'s, 'w, T, CELLS, GRAPHEME_WIDTH>
+{
+    pub fn new<S: TagSink<T>>(
+        sink: &'s mut S,
+        width: usize,
+        width_provider: &'w dyn WidthProvider,
+    ) -> Self {
+        Self::with_viewport(sink, width, width_provider, 0..usize::MAX)
+    }
+
+    /// Creates a buffer that only streams the lines in `lines`. Writes to other lines are
+    /// discarded, but still count towards `total_height`.
+    ///
+    /// Panics if `width` is 0 or larger than `CELLS`, since the window must hold at least one line.
+    pub fn with_viewport<S: TagSink<T>>(
+        sink: &'s mut S,
+        width: usize,
+        width_provider: &'w dyn WidthProvider,
+        lines: Range<usize>,
+    ) -> Self {
+        assert!(
+            width > 0 && width <= CELLS,
+            "Expected a width between 1 and {CELLS}, got {width}"
+        );
+
+        Self {
+            cells: core::array::from_fn(|_| Cell::Empty),
+            width,
+            flushed: lines.start,
+            end: lines.end,
+            watermark: 0,
+            lowest_written_line: None,
+            width_provider,
+            control_policy: ControlPolicy::default(),
+            diagnostics: Diagnostics::default(),
+            sink,
+            halted: false,
+        }
+    }
+
+    /// Sets what canvases writing to this buffer put in place of control characters.
+    pub fn with_control_policy(mut self, control_policy: ControlPolicy) -> Self {
+        self.control_policy = control_policy;
+        self
+    }
+
+    /// Returns the diagnostics reported so far.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the diagnostics reported so far.
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        mem::take(&mut self.diagnostics)
+    }
+
+    fn height(&self) -> usize {
+        CELLS / self.width
+    }
+
+    /// Returns the first line that has not been handed to the sink yet.
+    pub fn flushed_lines(&self) -> usize {
+        self.flushed
+    }
+
+    /// Returns the number of lines the renderable has written to, including those outside the
+    /// viewport.
+    pub fn total_height(&self) -> usize {
+        self.lowest_written_line.map_or(0, |line| line + 1)
+    }
+
+    /// Flushes every line that has been written, ending the stream.
+    pub fn finish(mut self) {
+        while self.has_pending_line() {
+            self.flush_line();
+        }
+    }
+
+    fn has_pending_line(&self) -> bool {
+        self.flushed < self.end
+            && self
+                .lowest_written_line
+                .is_some_and(|line| self.flushed <= line)
+    }
+
+    fn flush_committed(&mut self) {
+        while self.flushed < self.watermark && self.has_pending_line() {
+            self.flush_line();
+        }
+    }
+
+    fn flush_line(&mut self) {
+        let start = (self.flushed % self.height()) * self.width;
+        let row = &mut self.cells[start..start + self.width];
+
+        self.flushed += 1;
+
+        if self.halted {
+            row.fill_with(|| Cell::Empty);
+            return;
+        }
+
+        let result = dispatch_row(row.iter_mut().map(mem::take), self.sink);
+
+        if result.is_break() {
+            row.fill_with(|| Cell::Empty);
+            self.halted = true;
+        }
+    }
+}
+
+impl<'s, 'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize> RenderBuffer<T>
+    for StreamingRenderBuffer<'s, 'w, T, CELLS, GRAPHEME_WIDTH>
+{
+    fn can_set_cell(&self, position: RenderPosition, c: &gph) -> bool {
+        position.column() + c.width(self.width_provider) <= self.width
+    }
+
+    fn set_cell(&mut self, position: RenderPosition, c: &gph, tag: T) -> bool {
+        if !self.can_set_cell(position, c) {
+            return false;
+        }
+
+        let line = position.line();
+
+        if self.lowest_written_line.is_none_or(|lowest| line > lowest) {
+            self.lowest_written_line = Some(line);
+        }
+
+        if line < self.flushed.max(self.watermark) || line >= self.end {
+            return true;
+        }
+
+        while line >= self.flushed + self.height() {
+            self.flush_line();
+        }
+
+        let row_start = (line % self.height()) * self.width;
+        let grapheme = StaticGrapheme::from_single_grapheme(c);
+
+        if grapheme.did_overflow() {
+            self.report(Diagnostic::new(
+                DiagnosticKind::UnrepresentableGrapheme,
+                position,
+            ));
+        }
+
+        write_to_row(
+            &mut self.cells[row_start..row_start + self.width],
+            position.column(),
+            c.width(self.width_provider),
+            tag,
+            grapheme,
+        );
+
+        self.flush_committed();
+
+        true
+    }
+
+    fn width(&self) -> Option<usize> {
+        Some(self.width)
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.width_provider
+    }
+
+    fn control_policy(&self) -> ControlPolicy {
+        self.control_policy
+    }
+
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn get_cell(&self, position: RenderPosition) -> Option<(&gph, &T)> {
+        if !self.readable_lines().contains(&position.line()) || position.column() >= self.width {
+            return None;
+        }
+
+        let index = (position.line() % self.height()) * self.width + position.column();
+
+        self.cells[index].get()
+    }
+
+    fn readable_lines(&self) -> Range<usize> {
+        self.flushed..self.flushed + self.height()
+    }
+
+    fn set_line_watermark(&mut self, line: usize) {
+        self.watermark = self.watermark.max(line);
+        self.flush_committed();
+    }
+}
+
+impl<'s, 'w, T: Tag, const CELLS: usize, const GRAPHEME_WIDTH: usize, R: Renderable<T>>
+    RenderDispatcher<T, R> for StreamingRenderBuffer<'s, 'w, T, CELLS, GRAPHEME_WIDTH>
+{
+    fn render_with_diagnostics<S: TagSink<T>>(
+        mut sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        control_policy: ControlPolicy,
+    ) -> (S::Result, Diagnostics) {
+        let mut buffer = StreamingRenderBuffer::<T, CELLS, GRAPHEME_WIDTH>::new(
+            &mut sink,
+            width,
+            width_provider,
+        )
+        .with_control_policy(control_policy);
+        let mut canvas = buffer.canvas_at(RenderPosition::zero());
+
+        if let Err(e) = renderable.render_into(&mut canvas) {
+            let diagnostics = buffer.take_diagnostics();
+            return (S::Result::from(e), diagnostics);
+        }
+
+        let diagnostics = buffer.take_diagnostics();
+        buffer.finish();
+
+        (sink.finalize(), diagnostics)
+    }
+
+    fn render_viewport<S: TagSink<T>>(
+        mut sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        lines: Range<usize>,
+        control_policy: ControlPolicy,
+    ) -> ViewportResult<S::Result> {
+        let mut buffer = StreamingRenderBuffer::<T, CELLS, GRAPHEME_WIDTH>::with_viewport(
+            &mut sink,
+            width,
+            width_provider,
+            lines,
+        )
+        .with_control_policy(control_policy);
+        let mut canvas = buffer.canvas_at(RenderPosition::zero());
+
+        if let Err(e) = renderable.render_into(&mut canvas) {
+            return ViewportResult {
+                result: S::Result::from(e),
+                total_height: buffer.total_height(),
+            };
+        }
+
+        let total_height = buffer.total_height();
+        buffer.finish();
+
+        ViewportResult {
+            result: sink.finalize(),
+            total_height,
+        }
+    }
+}
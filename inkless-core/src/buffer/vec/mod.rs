@@ -0,0 +1,234 @@
+use alloc::vec::Vec;
+use core::{
+    mem,
+    ops::{ControlFlow, Range},
+};
+
+use crate::{
+    buffer::{
+        RenderBuffer, RenderDispatcher, ViewportResult,
+        cell::{Cell, dispatch_row, write_to_row},
+        control::ControlPolicy,
+    },
+    canvas::ext::RenderBufferCanvasExt,
+    diagnostics::{Diagnostic, Diagnostics},
+    grapheme::{gph, grapheme::Grapheme, width::WidthProvider},
+    render_position::RenderPosition,
+    renderable::Renderable,
+    tag::{Tag, sink::TagSink},
+};
+
+/// A heap-backed render buffer that grows one row at a time as lines are written.
+///
+/// Unlike `StaticRenderBuffer`, the whole renderable fits in memory at once, so it is rendered
+/// exactly once before its rows are streamed to the `TagSink`.
+#[derive(Debug)]
+pub struct VecRenderBuffer<'w, T: Tag> {
+    rows: Vec<Vec<Cell<T, Grapheme>>>,
+    width: usize,
+    lines: Range<usize>,
+    lowest_written_line: Option<usize>,
+    width_provider: &'w dyn WidthProvider,
+    control_policy: ControlPolicy,
+    diagnostics: Diagnostics,
+}
+
+impl<'w, T: Tag> VecRenderBuffer<'w, T> {
+    pub fn new(width: usize, width_provider: &'w dyn WidthProvider) -> Self {
+        Self::with_viewport(width, width_provider, 0..usize::MAX)
+    }
+
+    /// Creates a buffer that only stores the lines in `lines`. Writes to other lines are discarded,
+    /// but still count towards `total_height`.
+    pub fn with_viewport(
+        width: usize,
+        width_provider: &'w dyn WidthProvider,
+        lines: Range<usize>,
+    ) -> Self {
+        Self {
+            rows: Vec::new(),
+            width,
+            lines,
+            lowest_written_line: None,
+            width_provider,
+            control_policy: ControlPolicy::default(),
+            diagnostics: Diagnostics::default(),
+        }
+    }
+
+    /// Sets what canvases writing to this buffer put in place of control characters.
+    pub fn with_control_policy(mut self, control_policy: ControlPolicy) -> Self {
+        self.control_policy = control_policy;
+        self
+    }
+
+    /// Returns the diagnostics reported so far.
+    pub fn diagnostics(&self) -> &Diagnostics {
+        &self.diagnostics
+    }
+
+    /// Removes and returns the diagnostics reported so far.
+    pub fn take_diagnostics(&mut self) -> Diagnostics {
+        mem::take(&mut self.diagnostics)
+    }
+
+    /// Returns the number of stored lines (including blank lines above the lowest one).
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Returns the number of lines the renderable has written to, including those outside the
+    /// viewport.
+    pub fn total_height(&self) -> usize {
+        self.lowest_written_line.map_or(0, |line| line + 1)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the row for `line`, growing the buffer with blank rows of the full width so that
+    /// lines never written to still reach the sink as `width` gaps.
+    fn row_mut(&mut self, line: usize) -> &mut Vec<Cell<T, Grapheme>> {
+        if line >= self.rows.len() {
+            let width = self.width;
+
+            self.rows
+                .resize_with(line + 1, || (0..width).map(|_| Cell::Empty).collect());
+        }
+
+        &mut self.rows[line]
+    }
+
+    /// Passes the first `height` lines to the sink, including blank lines below the last one
+    /// written.
+    fn dispatch<S: TagSink<T>>(mut self, sink: &mut S, height: usize) -> ControlFlow<()> {
+        if height > self.rows.len() {
+            let width = self.width;
+
+            self.rows
+                .resize_with(height, || (0..width).map(|_| Cell::Empty).collect());
+        }
+
+        for row in self.rows {
+            dispatch_row(row, sink)?;
+        }
+
+        ControlFlow::Continue(())
+    }
+}
+
+impl<'w, T: Tag> RenderBuffer<T> for VecRenderBuffer<'w, T> {
+    fn can_set_cell(&self, position: RenderPosition, c: &gph) -> bool {
+        position.column() + c.width(self.width_provider) <= self.width
+    }
+
+    fn set_cell(&mut self, position: RenderPosition, c: &gph, tag: T) -> bool {
+        if !self.can_set_cell(position, c) {
+            return false;
+        }
+
+        let line = position.line();
+
+        if self.lowest_written_line.is_none_or(|lowest| line > lowest) {
+            self.lowest_written_line = Some(line);
+        }
+
+        if self.lines.contains(&line) {
+            let row = line - self.lines.start;
+
+            let width = c.width(self.width_provider);
+
+            write_to_row(
+                self.row_mut(row),
+                position.column(),
+                width,
+                tag,
+                Grapheme::from(c),
+            );
+        }
+
+        true
+    }
+
+    fn width(&self) -> Option<usize> {
+        Some(self.width)
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.width_provider
+    }
+
+    fn control_policy(&self) -> ControlPolicy {
+        self.control_policy
+    }
+
+    fn report(&mut self, diagnostic: Diagnostic) {
+        self.diagnostics.push(diagnostic);
+    }
+
+    fn get_cell(&self, position: RenderPosition) -> Option<(&gph, &T)> {
+        let row = position.line().checked_sub(self.lines.start)?;
+
+        self.rows.get(row)?.get(position.column())?.get()
+    }
+
+    fn readable_lines(&self) -> Range<usize> {
+        self.lines.start..self.lines.start + self.rows.len()
+    }
+}
+
+impl<'w, T: Tag, R: Renderable<T>> RenderDispatcher<T, R> for VecRenderBuffer<'w, T> {
+    fn render_with_diagnostics<S: TagSink<T>>(
+        mut sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        control_policy: ControlPolicy,
+    ) -> (S::Result, Diagnostics) {
+        let mut buffer =
+            VecRenderBuffer::new(width, width_provider).with_control_policy(control_policy);
+        let mut canvas = buffer.canvas_at(RenderPosition::zero());
+
+        if let Err(e) = renderable.render_into(&mut canvas) {
+            return (S::Result::from(e), buffer.take_diagnostics());
+        }
+
+        let diagnostics = buffer.take_diagnostics();
+        let height = buffer.height();
+
+        // A `Break` means the sink has given up; it reports why through `finalize`.
+        let _ = buffer.dispatch(&mut sink, height);
+
+        (sink.finalize(), diagnostics)
+    }
+
+    fn render_viewport<S: TagSink<T>>(
+        mut sink: S,
+        renderable: R,
+        width: usize,
+        width_provider: &dyn WidthProvider,
+        lines: Range<usize>,
+        control_policy: ControlPolicy,
+    ) -> ViewportResult<S::Result> {
+        let mut buffer = VecRenderBuffer::with_viewport(width, width_provider, lines.clone())
+            .with_control_policy(control_policy);
+        let mut canvas = buffer.canvas_at(RenderPosition::zero());
+
+        if let Err(e) = renderable.render_into(&mut canvas) {
+            return ViewportResult {
+                result: S::Result::from(e),
+                total_height: buffer.total_height(),
+            };
+        }
+
+        let total_height = buffer.total_height();
+        let height = lines.end.min(total_height).saturating_sub(lines.start);
+        let _ = buffer.dispatch(&mut sink, height);
+
+        ViewportResult {
+            result: sink.finalize(),
+            total_height,
+        }
+    }
+}
@@ -1 +1,2 @@
+pub mod renderables;
 pub mod sinks;
@@ -0,0 +1,199 @@
+use core::any::{Any, TypeId};
+
+use crate::{
+    canvas::{Canvas, adapter::AdaptedRenderable, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
+    render_position::RenderPosition,
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// Renders `inner`, declaring every line above a written grapheme finished.
+///
+/// Renderables never commit lines themselves, since they cannot know whether whatever contains them
+/// will write there again. Wrap the top-level renderable in this when it fills the output from top
+/// to bottom, so a `StreamingRenderBuffer` can flush lines before the render ends. Anything written
+/// above a line that was already written below is dropped by such a buffer.
+pub struct CommitLines<R> {
+    inner: R,
+}
+
+impl<R> CommitLines<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+impl<T: Tag, R: Renderable<T>> Renderable<T> for CommitLines<R> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        CommittingCanvas::new(canvas).write(&self.inner).map(|_| ())
+    }
+}
+
+/// A canvas that sets the line watermark to the line of every grapheme written through it, and
+/// forwards everything else.
+pub struct CommittingCanvas<'a, T: Tag> {
+    inner: &'a mut dyn Canvas<T>,
+}
+
+impl<'a, T: Tag> CommittingCanvas<'a, T> {
+    pub fn new(inner: &'a mut dyn Canvas<T>) -> Self {
+        Self { inner }
+    }
+
+    fn commit(&mut self, line: usize, written: bool) -> bool {
+        if written {
+            self.inner.set_line_watermark(line);
+        }
+
+        written
+    }
+}
+
+impl<'a, T: Tag> Canvas<T> for CommittingCanvas<'a, T> {
+    fn write(&mut self, renderable: &dyn Renderable<T>) -> Result<CanvasSummary, RenderableError> {
+        self.inner
+            .write(&AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut CommittingCanvas::new(canvas))
+            }))
+    }
+
+    fn set_char(&mut self, ch: char, tag: T) -> bool {
+        let line = self.inner.get_position().line();
+        let written = self.inner.set_char(ch, tag);
+
+        self.commit(line, written)
+    }
+
+    fn set_gph(&mut self, v: &gph, tag: T) -> bool {
+        let line = self.inner.get_position().line();
+        let written = self.inner.set_gph(v, tag);
+
+        self.commit(line, written)
+    }
+
+    fn can_set_gph(&mut self, v: &gph) -> bool {
+        self.inner.can_set_gph(v)
+    }
+
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
+    fn get_start_position(&self) -> RenderPosition {
+        self.inner.get_start_position()
+    }
+
+    fn get_position(&self) -> RenderPosition {
+        self.inner.get_position()
+    }
+
+    fn set_position(&mut self, position: RenderPosition) -> &mut dyn Canvas<T> {
+        self.inner.set_position(position);
+        self
+    }
+
+    fn set_column(&mut self, column: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_column(column);
+        self
+    }
+
+    fn set_line(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line(line);
+        self
+    }
+
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
+    fn cursor_down(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down();
+        self
+    }
+
+    fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down_by(count);
+        self
+    }
+
+    fn try_cursor_up(&mut self) -> bool {
+        self.inner.try_cursor_up()
+    }
+
+    fn try_cursor_up_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_up_by(count)
+    }
+
+    fn cursor_up(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up();
+        self
+    }
+
+    fn cursor_up_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up_by(count);
+        self
+    }
+
+    fn try_cursor_left(&mut self) -> bool {
+        self.inner.try_cursor_left()
+    }
+
+    fn try_cursor_left_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_left_by(count)
+    }
+
+    fn cursor_left(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left();
+        self
+    }
+
+    fn cursor_left_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left_by(count);
+        self
+    }
+
+    fn cursor_right(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right();
+        self
+    }
+
+    fn cursor_right_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right_by(count);
+        self
+    }
+}
@@ -0,0 +1,4 @@
+pub mod commit_lines;
+pub mod overlay;
+pub mod with_environment;
+pub mod with_tab_stops;
@@ -0,0 +1,266 @@
+use core::any::{Any, TypeId};
+
+use crate::{
+    canvas::{
+        Canvas, adapter::AdaptedRenderable, summary::CanvasSummary, tab_stops::TabStops,
+        uncommitted::UncommittedCanvas,
+    },
+    diagnostics::DiagnosticKind,
+    grapheme::{char::CharGrapheme, gph, width::WidthProvider},
+    render_position::RenderPosition,
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// Renders `overlay` on top of `base`, both starting at the current position.
+///
+/// Cells the overlay does not write to are transparent and keep the base's content. When the overlay
+/// covers only one half of a wide grapheme, the uncovered half is replaced with spaces carrying the
+/// overlay's tag. Overlays nest, so `Overlay::new(Overlay::new(a, b), c)` draws three layers in
+/// z-order.
+///
+/// The cursor is left where the base ended. Line watermarks set by either layer are dropped, since
+/// the overlay goes back over lines the base has already moved past.
+pub struct Overlay<B, O> {
+    base: B,
+    overlay: O,
+}
+
+impl<B, O> Overlay<B, O> {
+    pub fn new(base: B, overlay: O) -> Self {
+        Self { base, overlay }
+    }
+}
+
+impl<T: Tag + Clone, B: Renderable<T>, O: Renderable<T>> Renderable<T> for Overlay<B, O> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        let start = canvas.get_position();
+
+        UncommittedCanvas::new(canvas).write(&self.base)?;
+
+        let end = canvas.get_position();
+
+        canvas.set_position(start);
+        OverlayCanvas::new(&mut UncommittedCanvas::new(canvas)).write(&self.overlay)?;
+        canvas.set_position(end);
+
+        Ok(())
+    }
+}
+
+/// A canvas that writes over existing content, blanking out any wide grapheme it partially covers.
+pub struct OverlayCanvas<'a, T: Tag> {
+    inner: &'a mut dyn Canvas<T>,
+
+    /// The position just past the last grapheme written, where nothing to the left can reach.
+    written_to: Option<RenderPosition>,
+}
+
+impl<'a, T: Tag + Clone> OverlayCanvas<'a, T> {
+    pub fn new(inner: &'a mut dyn Canvas<T>) -> Self {
+        Self {
+            inner,
+            written_to: None,
+        }
+    }
+
+    fn width_at(&self, line: usize, column: usize) -> Option<usize> {
+        self.inner
+            .get_gph(RenderPosition::new(line, column))
+            .map(|grapheme| grapheme.width(self.inner.width_provider()))
+    }
+
+    /// Replaces every cell that will be left holding part of a wide grapheme once `width` columns
+    /// starting at `position` are overwritten.
+    fn clear_covered(&mut self, position: RenderPosition, width: usize, tag: &T) {
+        let line = position.line();
+        let mut start = position.column();
+        let mut end = start + width;
+
+        // A wide grapheme to the left whose right half reaches under the new one. Runs of graphemes
+        // only need to look once, since every later one starts where this canvas last wrote.
+        if self.written_to != Some(position)
+            && let Some((column, covers)) = (0..start)
+                .rev()
+                .find_map(|column| self.width_at(line, column).map(|width| (column, width)))
+            && column + covers > start
+        {
+            start = column;
+        }
+
+        // A wide grapheme under the new one whose right half sticks out past it.
+        for column in position.column()..position.column() + width {
+            if let Some(covers) = self.width_at(line, column) {
+                end = end.max(column + covers);
+            }
+        }
+
+        for column in start..end {
+            self.inner.set_position(RenderPosition::new(line, column));
+            self.inner.set_char(' ', tag.clone());
+        }
+
+        self.inner.set_position(position);
+    }
+}
+
+impl<'a, T: Tag + Clone> Canvas<T> for OverlayCanvas<'a, T> {
+    fn write(&mut self, renderable: &dyn Renderable<T>) -> Result<CanvasSummary, RenderableError> {
+        self.inner
+            .write(&AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut OverlayCanvas::new(canvas))
+            }))
+    }
+
+    fn set_char(&mut self, ch: char, tag: T) -> bool {
+        self.set_gph(&CharGrapheme::from(ch), tag)
+    }
+
+    fn set_gph(&mut self, v: &gph, tag: T) -> bool {
+        // Tabs only move the cursor, so there is nothing underneath to clear.
+        if v.as_str() == "\t" {
+            return self.inner.set_gph(v, tag);
+        }
+
+        let position = self.inner.get_position();
+
+        if !self.inner.can_set_gph(v) {
+            return false;
+        }
+
+        self.inner.set_position(position);
+        self.clear_covered(position, v.width(self.inner.width_provider()), &tag);
+
+        let written = self.inner.set_gph(v, tag);
+
+        if written {
+            self.written_to = Some(self.inner.get_position());
+        }
+
+        written
+    }
+
+    fn can_set_gph(&mut self, v: &gph) -> bool {
+        self.inner.can_set_gph(v)
+    }
+
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
+    fn get_start_position(&self) -> RenderPosition {
+        self.inner.get_start_position()
+    }
+
+    fn get_position(&self) -> RenderPosition {
+        self.inner.get_position()
+    }
+
+    fn set_position(&mut self, position: RenderPosition) -> &mut dyn Canvas<T> {
+        self.inner.set_position(position);
+        self
+    }
+
+    fn set_column(&mut self, column: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_column(column);
+        self
+    }
+
+    fn set_line(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line(line);
+        self
+    }
+
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
+    fn cursor_down(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down();
+        self
+    }
+
+    fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down_by(count);
+        self
+    }
+
+    fn try_cursor_up(&mut self) -> bool {
+        self.inner.try_cursor_up()
+    }
+
+    fn try_cursor_up_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_up_by(count)
+    }
+
+    fn cursor_up(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up();
+        self
+    }
+
+    fn cursor_up_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up_by(count);
+        self
+    }
+
+    fn try_cursor_left(&mut self) -> bool {
+        self.inner.try_cursor_left()
+    }
+
+    fn try_cursor_left_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_left_by(count)
+    }
+
+    fn cursor_left(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left();
+        self
+    }
+
+    fn cursor_left_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left_by(count);
+        self
+    }
+
+    fn cursor_right(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right();
+        self
+    }
+
+    fn cursor_right_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right_by(count);
+        self
+    }
+}
@@ -0,0 +1,39 @@
+use core::marker::PhantomData;
+
+use crate::{
+    canvas::{
+        Canvas,
+        environment::{EnvironmentCanvas, EnvironmentKey},
+    },
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// Renders `inner` with `value` set for the environment key `K`, so it and every renderable nested
+/// inside it can read the value through `dyn Canvas::env`.
+pub struct WithEnvironment<K: EnvironmentKey, R> {
+    value: K::Value,
+    inner: R,
+    _key: PhantomData<K>,
+}
+
+impl<K: EnvironmentKey, R> WithEnvironment<K, R> {
+    pub fn new(value: K::Value, inner: R) -> Self {
+        Self {
+            value,
+            inner,
+            _key: PhantomData,
+        }
+    }
+}
+
+impl<T: Tag, K: EnvironmentKey, R: Renderable<T>> Renderable<T> for WithEnvironment<K, R> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        EnvironmentCanvas::<T, K>::new(canvas, &self.value)
+            .write(&self.inner)
+            .map(|_| ())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::{
+    canvas::{Canvas, tab_stops::TabStops},
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// Renders `inner` with its tabs expanded to `tab_stops` instead of the canvas's.
+pub struct WithTabStops<R> {
+    tab_stops: TabStops,
+    inner: R,
+}
+
+impl<R> WithTabStops<R> {
+    pub fn new(tab_stops: TabStops, inner: R) -> Self {
+        Self { tab_stops, inner }
+    }
+}
+
+impl<T: Tag, R: Renderable<T>> Renderable<T> for WithTabStops<R> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        let previous = canvas.tab_stops();
+
+        canvas.set_tab_stops(self.tab_stops);
+        let result = canvas.write(&self.inner);
+        canvas.set_tab_stops(previous);
+
+        result.map(|_| ())
+    }
+}
@@ -0,0 +1,204 @@
+use crate::writer::character::CharacterWriter;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LineEnding {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
+}
+
+/// How a text sink lays out the whitespace between and after lines.
+///
+/// The default writes a space for every gap and ends every line, including the last, with `\n`.
+/// Sinks apply it through a `LineFormatter`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LineFormat {
+    trim_trailing_gaps: bool,
+    line_ending: LineEnding,
+    omit_final_newline: bool,
+}
+
+impl LineFormat {
+    /// Drops the gaps at the end of each line instead of padding it to the buffer width.
+    pub fn with_trim_trailing_gaps(mut self, trim_trailing_gaps: bool) -> Self {
+        self.trim_trailing_gaps = trim_trailing_gaps;
+        self
+    }
+
+    pub fn with_line_ending(mut self, line_ending: LineEnding) -> Self {
+        self.line_ending = line_ending;
+        self
+    }
+
+    /// Whether the last line is followed by a line ending.
+    pub fn with_final_newline(mut self, final_newline: bool) -> Self {
+        self.omit_final_newline = !final_newline;
+        self
+    }
+}
+
+/// Writes the whitespace of one sink's output according to a `LineFormat`.
+///
+/// Trailing gaps and line endings are held back until the formatter knows they are wanted, so a
+/// sink calls `content` before writing anything else to its writer.
+#[derive(Clone, Debug, Default)]
+pub struct LineFormatter {
+    format: LineFormat,
+
+    pending_gaps: usize,
+    pending_line_ending: bool,
+}
+
+impl LineFormatter {
+    pub fn new(format: LineFormat) -> Self {
+        Self {
+            format,
+            pending_gaps: 0,
+            pending_line_ending: false,
+        }
+    }
+
+    /// Writes the whitespace held back so far, ahead of content about to be written.
+    pub fn content<W: CharacterWriter + ?Sized>(&mut self, writer: &mut W) -> Result<(), W::Error> {
+        if self.pending_line_ending {
+            self.pending_line_ending = false;
+            writer.write_str(self.format.line_ending.as_str())?;
+        }
+
+        while self.pending_gaps > 0 {
+            self.pending_gaps -= 1;
+            writer.write_str(" ")?;
+        }
+
+        Ok(())
+    }
+
+    pub fn gap<W: CharacterWriter + ?Sized>(&mut self, writer: &mut W) -> Result<(), W::Error> {
+        if self.format.trim_trailing_gaps {
+            self.pending_gaps += 1;
+            Ok(())
+        } else {
+            self.content(writer)?;
+            writer.write_str(" ")
+        }
+    }
+
+    pub fn end_line<W: CharacterWriter + ?Sized>(
+        &mut self,
+        writer: &mut W,
+    ) -> Result<(), W::Error> {
+        self.pending_gaps = 0;
+
+        if self.pending_line_ending {
+            writer.write_str(self.format.line_ending.as_str())?;
+        }
+
+        self.pending_line_ending = true;
+        Ok(())
+    }
+
+    /// Writes the final line ending, if one is wanted.
+    pub fn finish<W: CharacterWriter + ?Sized>(&mut self, writer: &mut W) -> Result<(), W::Error> {
+        self.pending_gaps = 0;
+
+        if self.pending_line_ending && !self.format.omit_final_newline {
+            self.pending_line_ending = false;
+            writer.write_str(self.format.line_ending.as_str())?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fixed-size writer, so these tests also run without `alloc`.
+    struct Output {
+        bytes: [u8; 64],
+        len: usize,
+    }
+
+    impl Output {
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.bytes[..self.len]).unwrap()
+        }
+    }
+
+    impl CharacterWriter for Output {
+        type Error = ();
+
+        fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(())?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    /// Writes `line`s, where `_` is a gap, through a formatter for `format`.
+    fn format(format: LineFormat, lines: &[&str]) -> Output {
+        let mut out = Output {
+            bytes: [0; 64],
+            len: 0,
+        };
+        let mut formatter = LineFormatter::new(format);
+
+        for line in lines {
+            for ch in line.chars() {
+                if ch == '_' {
+                    formatter.gap(&mut out).unwrap();
+                } else {
+                    formatter.content(&mut out).unwrap();
+                    out.write_str(ch.encode_utf8(&mut [0; 4])).unwrap();
+                }
+            }
+
+            formatter.end_line(&mut out).unwrap();
+        }
+
+        formatter.finish(&mut out).unwrap();
+        out
+    }
+
+    #[test]
+    fn pads_gaps_by_default() {
+        assert_eq!(
+            format(LineFormat::default(), &["a_b__", "___"]).as_str(),
+            "a b  \n   \n"
+        );
+    }
+
+    #[test]
+    fn trims_only_trailing_gaps() {
+        let trim = LineFormat::default().with_trim_trailing_gaps(true);
+
+        assert_eq!(
+            format(trim, &["a__b__", "___", "_c"]).as_str(),
+            "a  b\n\n c\n"
+        );
+    }
+
+    #[test]
+    fn holds_back_the_final_line_ending() {
+        let format_crlf = LineFormat::default()
+            .with_line_ending(LineEnding::CrLf)
+            .with_final_newline(false);
+
+        assert_eq!(format(format_crlf, &["a", "", "b"]).as_str(), "a\r\n\r\nb");
+        assert_eq!(format(format_crlf, &[]).as_str(), "");
+    }
+}
@@ -1 +1,2 @@
+pub mod line_format;
 pub mod plaintext;
@@ -3,6 +3,7 @@ use core::ops::ControlFlow;
 use thiserror::Error;
 
 use crate::{
+    builtins::sinks::line_format::{LineFormat, LineFormatter},
     grapheme::gph,
     renderable::RenderableError,
     tag::{Tag, sink::TagSink},
@@ -12,6 +13,7 @@ use crate::{
 pub struct Plaintext<W: CharacterWriter> {
     writer: W,
     result: Result<(), W::Error>,
+    format: LineFormatter,
 }
 
 impl<W: CharacterWriter> From<W> for Plaintext<W> {
@@ -19,10 +21,18 @@ impl<W: CharacterWriter> From<W> for Plaintext<W> {
         Plaintext {
             writer: value,
             result: Ok(()),
+            format: LineFormatter::default(),
         }
     }
 }
 
+impl<W: CharacterWriter> Plaintext<W> {
+    pub fn with_line_format(mut self, format: LineFormat) -> Self {
+        self.format = LineFormatter::new(format);
+        self
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum PlaintextError<E> {
     Renderable(#[from] RenderableError),
@@ -33,7 +43,10 @@ impl<W: CharacterWriter, T: Tag> TagSink<T> for Plaintext<W> {
     type Result = Result<W, PlaintextError<W::Error>>;
 
     fn append(&mut self, grapheme: &gph, _: T) -> core::ops::ControlFlow<()> {
-        self.result = self.writer.write_str(grapheme.as_str());
+        self.result = self
+            .format
+            .content(&mut self.writer)
+            .and_then(|_| self.writer.write_str(grapheme.as_str()));
 
         if self.result.is_err() {
             ControlFlow::Break(())
@@ -43,7 +56,7 @@ impl<W: CharacterWriter, T: Tag> TagSink<T> for Plaintext<W> {
     }
 
     fn gap(&mut self) -> ControlFlow<()> {
-        self.result = self.writer.write_str(" ");
+        self.result = self.format.gap(&mut self.writer);
 
         if self.result.is_err() {
             ControlFlow::Break(())
@@ -53,7 +66,7 @@ impl<W: CharacterWriter, T: Tag> TagSink<T> for Plaintext<W> {
     }
 
     fn finalize_line(&mut self) -> ControlFlow<()> {
-        self.result = self.writer.write_str("\n");
+        self.result = self.format.end_line(&mut self.writer);
 
         if self.result.is_err() {
             ControlFlow::Break(())
@@ -62,7 +75,11 @@ impl<W: CharacterWriter, T: Tag> TagSink<T> for Plaintext<W> {
         }
     }
 
-    fn finalize(self) -> Self::Result {
+    fn finalize(mut self) -> Self::Result {
+        if self.result.is_ok() {
+            self.result = self.format.finish(&mut self.writer);
+        }
+
         self.result
             .map_err(PlaintextError::Writer)
             .map(|_| self.writer)
@@ -0,0 +1,33 @@
+use crate::{
+    canvas::Canvas,
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// Renders `renderable` into an adapter canvas wrapped around `canvas`.
+pub type Adapt<Tt, Ot> =
+    fn(canvas: &mut dyn Canvas<Tt>, renderable: &dyn Renderable<Ot>) -> Result<(), RenderableError>;
+
+/// Renders a renderable through an adapter canvas wrapped around whatever canvas it is written to.
+///
+/// Adapter canvases implement `write` by handing one of these to the canvas they wrap, so the
+/// canvas underneath starts the child canvas and tracks its `CanvasSummary`.
+pub struct AdaptedRenderable<'r, Tt: Tag, Ot: Tag> {
+    renderable: &'r dyn Renderable<Ot>,
+    adapt: Adapt<Tt, Ot>,
+}
+
+impl<'r, Tt: Tag, Ot: Tag> AdaptedRenderable<'r, Tt, Ot> {
+    pub fn new(renderable: &'r dyn Renderable<Ot>, adapt: Adapt<Tt, Ot>) -> Self {
+        Self { renderable, adapt }
+    }
+}
+
+impl<'r, Tt: Tag, Ot: Tag> Renderable<Tt> for AdaptedRenderable<'r, Tt, Ot> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<Tt>,
+    ) -> Result<(), RenderableError> {
+        (self.adapt)(canvas, self.renderable)
+    }
+}
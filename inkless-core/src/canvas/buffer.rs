@@ -1,8 +1,11 @@
+use core::any::{Any, TypeId};
+
 use crate::{
-    buffer::RenderBuffer,
-    canvas::{Canvas, summary::CanvasSummary},
-    grapheme::{char::CharGrapheme, gph},
-    render_position::RenderPosition,
+    buffer::{RenderBuffer, control::is_control},
+    canvas::{Canvas, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::{Diagnostic, DiagnosticKind},
+    grapheme::{char::CharGrapheme, gph, width::WidthProvider},
+    render_position::{Rect, RenderPosition, Size},
     renderable::{Renderable, RenderableError},
     tag::Tag,
 };
@@ -11,6 +14,9 @@ pub struct RenderBufferCanvas<'reference, T: Tag> {
     pub(super) buffer: &'reference mut (dyn RenderBuffer<T> + 'reference),
     pub(super) start_position: RenderPosition,
     pub(super) position: RenderPosition,
+    pub(super) tab_stops: TabStops,
+    pub(super) bounds: Option<Rect>,
+    pub(super) overflowed: bool,
 }
 
 impl<'r, T: Tag> RenderBufferCanvas<'r, T> {
@@ -19,22 +25,40 @@ impl<'r, T: Tag> RenderBufferCanvas<'r, T> {
         CanvasSummary {
             end_position: self.position,
             start_position: self.start_position,
+            bounds: self.bounds,
+            overflowed: self.overflowed,
         }
     }
+
+    fn record_written(&mut self, cells: Rect) {
+        self.bounds = Some(self.bounds.map_or(cells, |bounds| bounds.union(cells)));
+    }
+
+    /// Returns the column the next tab stop lands on, if a tab can be written at the head.
+    fn tab_target(&self) -> Option<usize> {
+        let column = self.position.column();
+        let relative = column.saturating_sub(self.start_position.column());
+        let target = column + self.tab_stops.advance(relative);
+
+        self.buffer
+            .width()
+            .is_none_or(|width| target <= width)
+            .then_some(target)
+    }
 }
 
 impl<'reference, T: Tag> Canvas<T> for RenderBufferCanvas<'reference, T> {
     /// Writes the renderable to the current position.
     ///
     /// Returns: `RenderBufferSummary` describing the start and end position.
-    fn write<'a>(
-        &'a mut self,
-        renderable: &dyn Renderable<T>,
-    ) -> Result<CanvasSummary, RenderableError> {
-        let mut new_canvas: RenderBufferCanvas<'a, T> = RenderBufferCanvas {
+    fn write(&mut self, renderable: &dyn Renderable<T>) -> Result<CanvasSummary, RenderableError> {
+        let mut new_canvas = RenderBufferCanvas {
             buffer: &mut *self.buffer,
             position: self.position,
             start_position: self.position,
+            tab_stops: self.tab_stops,
+            bounds: None,
+            overflowed: false,
         };
 
         renderable.render_into(&mut new_canvas)?;
@@ -42,6 +66,11 @@ impl<'reference, T: Tag> Canvas<T> for RenderBufferCanvas<'reference, T> {
         let result = new_canvas.end();
 
         self.position = result.end_position;
+        self.overflowed |= result.overflowed;
+
+        if let Some(bounds) = result.bounds {
+            self.record_written(bounds);
+        }
 
         Ok(result)
     }
@@ -61,10 +90,45 @@ impl<'reference, T: Tag> Canvas<T> for RenderBufferCanvas<'reference, T> {
     ///
     /// Returns: `false` (and does not mutate the buffer) if the grapheme horizontally overflowed
     fn set_gph(&mut self, v: &gph, tag: T) -> bool {
+        if v.as_str() == "\t" {
+            return match self.tab_target() {
+                Some(column) => {
+                    self.set_column(column);
+                    true
+                }
+                None => {
+                    self.overflowed = true;
+                    false
+                }
+            };
+        }
+
+        if is_control(v) {
+            let position = self.position;
+            let written = match self.buffer.control_policy().replacement(v) {
+                Some(replacement) => self.set_gph(&replacement, tag),
+                None => true,
+            };
+
+            if written {
+                self.buffer.report(Diagnostic::new(
+                    DiagnosticKind::UnrepresentableGrapheme,
+                    position,
+                ));
+            }
+
+            return written;
+        }
+
         let result = self.buffer.set_cell(self.position, v, tag);
 
         if result {
-            self.cursor_right_by(v.width(self.buffer.ambiguity_policy()));
+            let width = v.width(self.buffer.width_provider());
+
+            self.record_written(Rect::new(self.position, Size::new(1, width.max(1))));
+            self.cursor_right_by(width);
+        } else {
+            self.overflowed = true;
         }
 
         result
@@ -76,15 +140,63 @@ impl<'reference, T: Tag> Canvas<T> for RenderBufferCanvas<'reference, T> {
     ///
     /// Returns: `false` (and does not mutate the cursor) if the grapheme horizontally overflowed
     fn can_set_gph(&mut self, v: &gph) -> bool {
+        if v.as_str() == "\t" {
+            return match self.tab_target() {
+                Some(column) => {
+                    self.set_column(column);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        if is_control(v) {
+            return match self.buffer.control_policy().replacement(v) {
+                Some(replacement) => self.can_set_gph(&replacement),
+                None => true,
+            };
+        }
+
         let result = self.buffer.can_set_cell(self.position, v);
 
         if result {
-            self.cursor_right_by(v.width(self.buffer.ambiguity_policy()));
+            self.cursor_right_by(v.width(self.buffer.width_provider()));
         }
 
         result
     }
 
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.buffer.get_cell(position).map(|(grapheme, _)| grapheme)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.buffer
+            .width()
+            .map(|width| width.saturating_sub(self.start_position.column()))
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.tab_stops
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T> {
+        self.tab_stops = tab_stops;
+        self
+    }
+
+    fn get_environment(&self, _key: TypeId) -> Option<&dyn Any> {
+        None
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.buffer.report(Diagnostic::new(kind, self.position));
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.buffer.width_provider()
+    }
+
     fn get_start_position(&self) -> RenderPosition {
         self.start_position
     }
@@ -108,6 +220,11 @@ impl<'reference, T: Tag> Canvas<T> for RenderBufferCanvas<'reference, T> {
         self
     }
 
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.buffer.set_line_watermark(line);
+        self
+    }
+
     fn cursor_down(&mut self) -> &mut dyn Canvas<T> {
         self.position = self.position.down(1);
         self
@@ -0,0 +1,204 @@
+use core::any::{Any, TypeId};
+
+use crate::{
+    canvas::{Canvas, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
+    render_position::RenderPosition,
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// A typed key for a value in the render environment.
+///
+/// Keys are usually empty types, so the type itself names the value:
+///
+/// ```ignore
+/// pub struct ReducedMotion;
+///
+/// impl EnvironmentKey for ReducedMotion {
+///     type Value = bool;
+/// }
+/// ```
+pub trait EnvironmentKey: 'static {
+    type Value: Any;
+}
+
+impl<'c, T: Tag> dyn Canvas<T> + 'c {
+    /// Returns the value set for `K` by the nearest enclosing `WithEnvironment`, if any.
+    pub fn env<K: EnvironmentKey>(&self) -> Option<&K::Value> {
+        self.get_environment(TypeId::of::<K>())?.downcast_ref()
+    }
+}
+
+/// A canvas that answers `get_environment` for `K` with `value`, and forwards everything else.
+///
+/// Children written through it get the value as well.
+pub struct EnvironmentCanvas<'a, 'v, T: Tag, K: EnvironmentKey> {
+    inner: &'a mut dyn Canvas<T>,
+    value: &'v K::Value,
+}
+
+impl<'a, 'v, T: Tag, K: EnvironmentKey> EnvironmentCanvas<'a, 'v, T, K> {
+    pub fn new(inner: &'a mut dyn Canvas<T>, value: &'v K::Value) -> Self {
+        Self { inner, value }
+    }
+}
+
+/// Renders a renderable into an `EnvironmentCanvas` around the child canvas it is given.
+struct Scoped<'r, 'v, T: Tag, K: EnvironmentKey> {
+    renderable: &'r dyn Renderable<T>,
+    value: &'v K::Value,
+}
+
+impl<'r, 'v, T: Tag, K: EnvironmentKey> Renderable<T> for Scoped<'r, 'v, T, K> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        self.renderable
+            .render_into(&mut EnvironmentCanvas::<T, K>::new(canvas, self.value))
+    }
+}
+
+impl<'a, 'v, T: Tag, K: EnvironmentKey> Canvas<T> for EnvironmentCanvas<'a, 'v, T, K> {
+    fn write(&mut self, renderable: &dyn Renderable<T>) -> Result<CanvasSummary, RenderableError> {
+        self.inner.write(&Scoped::<T, K> {
+            renderable,
+            value: self.value,
+        })
+    }
+
+    fn set_char(&mut self, ch: char, tag: T) -> bool {
+        self.inner.set_char(ch, tag)
+    }
+
+    fn set_gph(&mut self, v: &gph, tag: T) -> bool {
+        self.inner.set_gph(v, tag)
+    }
+
+    fn can_set_gph(&mut self, v: &gph) -> bool {
+        self.inner.can_set_gph(v)
+    }
+
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        if key == TypeId::of::<K>() {
+            Some(self.value)
+        } else {
+            self.inner.get_environment(key)
+        }
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
+    fn get_start_position(&self) -> RenderPosition {
+        self.inner.get_start_position()
+    }
+
+    fn get_position(&self) -> RenderPosition {
+        self.inner.get_position()
+    }
+
+    fn set_position(&mut self, position: RenderPosition) -> &mut dyn Canvas<T> {
+        self.inner.set_position(position);
+        self
+    }
+
+    fn set_column(&mut self, column: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_column(column);
+        self
+    }
+
+    fn set_line(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line(line);
+        self
+    }
+
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
+    fn cursor_down(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down();
+        self
+    }
+
+    fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down_by(count);
+        self
+    }
+
+    fn try_cursor_up(&mut self) -> bool {
+        self.inner.try_cursor_up()
+    }
+
+    fn try_cursor_up_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_up_by(count)
+    }
+
+    fn cursor_up(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up();
+        self
+    }
+
+    fn cursor_up_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up_by(count);
+        self
+    }
+
+    fn try_cursor_left(&mut self) -> bool {
+        self.inner.try_cursor_left()
+    }
+
+    fn try_cursor_left_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_left_by(count)
+    }
+
+    fn cursor_left(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left();
+        self
+    }
+
+    fn cursor_left_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left_by(count);
+        self
+    }
+
+    fn cursor_right(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right();
+        self
+    }
+
+    fn cursor_right_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right_by(count);
+        self
+    }
+}
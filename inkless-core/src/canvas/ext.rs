@@ -1,5 +1,7 @@
 use crate::{
-    buffer::RenderBuffer, canvas::buffer::RenderBufferCanvas, render_position::RenderPosition,
+    buffer::RenderBuffer,
+    canvas::{buffer::RenderBufferCanvas, tab_stops::TabStops},
+    render_position::RenderPosition,
     tag::Tag,
 };
 
@@ -13,6 +15,9 @@ impl<T: Tag, B: RenderBuffer<T>> RenderBufferCanvasExt<T> for B {
             buffer: self,
             start_position: position,
             position,
+            tab_stops: TabStops::default(),
+            bounds: None,
+            overflowed: false,
         }
     }
 }
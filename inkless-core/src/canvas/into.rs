@@ -1,6 +1,9 @@
+use core::any::{Any, TypeId};
+
 use crate::{
-    canvas::{Canvas, summary::CanvasSummary},
-    grapheme::gph,
+    canvas::{Canvas, adapter::AdaptedRenderable, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
     render_position::RenderPosition,
     renderable::{Renderable, RenderableError},
     tag::Tag,
@@ -21,14 +24,23 @@ impl<'a, Tt: Tag, Ot: Tag + Into<Tt>> Canvas<Ot> for IntoCanvas<'a, Tt> {
         &'b mut self,
         renderable: &dyn Renderable<Ot>,
     ) -> Result<CanvasSummary, RenderableError> {
-        let start = self.inner.get_position();
-        renderable.render_into(self)?;
-        let end = self.inner.get_position();
+        self.inner
+            .write(&AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut IntoCanvas::new(canvas))
+            }))
+    }
 
-        Ok(CanvasSummary {
-            start_position: start,
-            end_position: end,
-        })
+    fn write_within(
+        &mut self,
+        parent: Ot,
+        renderable: &dyn Renderable<Ot>,
+    ) -> Result<CanvasSummary, RenderableError> {
+        self.inner.write_within(
+            parent.into(),
+            &AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut IntoCanvas::new(canvas))
+            }),
+        )
     }
 
     fn set_char(&mut self, ch: char, tag: Ot) -> bool {
@@ -44,6 +56,39 @@ impl<'a, Tt: Tag, Ot: Tag + Into<Tt>> Canvas<Ot> for IntoCanvas<'a, Tt> {
         self.inner.can_set_gph(v)
     }
 
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<Ot> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
     fn get_start_position(&self) -> RenderPosition {
         self.inner.get_start_position()
     }
@@ -67,6 +112,11 @@ impl<'a, Tt: Tag, Ot: Tag + Into<Tt>> Canvas<Ot> for IntoCanvas<'a, Tt> {
         self
     }
 
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
     fn cursor_down(&mut self) -> &mut dyn Canvas<Ot> {
         self.inner.cursor_down();
         self
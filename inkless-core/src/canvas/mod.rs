@@ -1,11 +1,20 @@
+pub mod adapter;
 pub mod buffer;
+pub mod cascade;
+pub mod environment;
 pub mod ext;
 pub mod into;
+pub mod region;
 pub mod summary;
+pub mod tab_stops;
+pub mod uncommitted;
+
+use core::any::{Any, TypeId};
 
 use crate::{
-    canvas::summary::CanvasSummary,
-    grapheme::gph,
+    canvas::{cascade::Cascade, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
     render_position::RenderPosition,
     renderable::{Renderable, RenderableError},
     tag::Tag,
@@ -26,6 +35,22 @@ pub trait Canvas<T: Tag> {
         renderable: &dyn Renderable<T>,
     ) -> Result<CanvasSummary, RenderableError>;
 
+    /// Writes the renderable to the current position, merging every tag it sets over `parent`
+    /// with `Tag::merge`, so it inherits whatever style it leaves unset.
+    ///
+    /// Canvases that translate tags should override this to translate `parent` as well, so the
+    /// merge happens on the tags that finally reach the buffer.
+    fn write_within(
+        &mut self,
+        parent: T,
+        renderable: &dyn Renderable<T>,
+    ) -> Result<CanvasSummary, RenderableError> {
+        self.write(&Cascade {
+            renderable,
+            parent: &parent,
+        })
+    }
+
     /// Sets the character at the canvas head using the provided tag.
     ///
     /// Returns: `true` (and mutates the canvas) if the character is entirely within the horizontal bounds of the buffer
@@ -38,6 +63,10 @@ pub trait Canvas<T: Tag> {
     /// Returns: `true` (and mutates the canvas) if the grapheme is entirely within the horizontal bounds of the buffer
     ///
     /// Returns: `false` (and does not mutate the buffer) if the grapheme horizontally overflowed
+    ///
+    /// A `\t` is not drawn; it moves the cursor to the next tab stop, leaving the cells it skips
+    /// untouched, and returns `false` if that stop is past the horizontal bounds of the buffer.
+    /// Other control characters are replaced according to the buffer's `ControlPolicy`.
     fn set_gph(&mut self, v: &gph, tag: T) -> bool;
 
     /// Checks to see if the grapheme at the canvas head can be printed.
@@ -47,6 +76,42 @@ pub trait Canvas<T: Tag> {
     /// Returns: `false` (and does not mutate the cursor) if the grapheme horizontally overflowed
     fn can_set_gph(&mut self, v: &gph) -> bool;
 
+    /// Returns the grapheme occupying the cell at `position`, if the underlying buffer can read it
+    /// back.
+    ///
+    /// Returns `None` for empty cells and for the cells covered by the right half of a wide grapheme.
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph>;
+
+    /// Returns the number of columns between the start column of this canvas and the right edge of
+    /// the buffer, or `None` if the buffer has no width limit.
+    fn available_width(&self) -> Option<usize>;
+
+    /// Returns the number of columns between the cursor and the right edge of the buffer, or `None`
+    /// if the buffer has no width limit.
+    fn remaining_columns(&self) -> Option<usize> {
+        self.available_width().map(|width| {
+            (self.get_start_position().column() + width)
+                .saturating_sub(self.get_position().column())
+        })
+    }
+
+    /// Returns the tab stops a `\t` advances to, relative to the start column of this canvas.
+    fn tab_stops(&self) -> TabStops;
+
+    /// Sets the tab stops for this canvas and every canvas written through it from now on.
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T>;
+
+    /// Returns the environment value set for the `EnvironmentKey` with the type id `key`.
+    ///
+    /// `dyn Canvas::env` is the typed way to call this.
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any>;
+
+    /// Reports a warning at the cursor without stopping the render.
+    fn report(&mut self, kind: DiagnosticKind);
+
+    /// Returns the provider the underlying buffer measures graphemes with.
+    fn width_provider(&self) -> &dyn WidthProvider;
+
     fn get_start_position(&self) -> RenderPosition;
 
     fn get_position(&self) -> RenderPosition;
@@ -57,6 +122,11 @@ pub trait Canvas<T: Tag> {
 
     fn set_line(&mut self, line: usize) -> &mut dyn Canvas<T>;
 
+    /// Declares that every line above `line` is finished and will never be written again.
+    ///
+    /// Streaming buffers use this to push completed lines to the sink before the render ends.
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T>;
+
     fn cursor_down(&mut self) -> &mut dyn Canvas<T>;
 
     fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<T>;
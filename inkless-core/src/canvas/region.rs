@@ -0,0 +1,349 @@
+use core::{
+    any::{Any, TypeId},
+    cell::Cell,
+};
+
+use crate::{
+    canvas::{Canvas, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{char::CharGrapheme, gph, width::WidthProvider},
+    render_position::{Rect, RenderPosition},
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+/// A canvas confined to a rectangle of another canvas.
+///
+/// Positions are relative to the rectangle's top-left corner, which is where the cursor starts.
+/// Graphemes that would not fit entirely inside the rectangle are rejected with `false`, the same
+/// way a buffer rejects horizontal overflow, and tabs expand relative to the rectangle's left edge.
+///
+/// Line watermarks are only forwarded when the region spans the full width of the canvas it wraps.
+/// A narrower region shares its lines with whatever is drawn beside it, so it cannot tell when they
+/// are finished.
+///
+/// Dropping a region made with `new` puts the inner canvas's cursor back where it was.
+pub struct RegionCanvas<'a, T: Tag> {
+    inner: &'a mut dyn Canvas<T>,
+    rect: Rect,
+    overflowed: bool,
+    start_position: RenderPosition,
+
+    /// Where to put the inner cursor back to on drop.
+    restore: Option<RenderPosition>,
+}
+
+/// Renders a renderable into a `RegionCanvas` around the child canvas it is given, reporting back
+/// whether the region rejected any grapheme.
+struct Confined<'r, T: Tag> {
+    renderable: &'r dyn Renderable<T>,
+    rect: Rect,
+    overflowed: &'r Cell<bool>,
+}
+
+impl<'r, T: Tag> Renderable<T> for Confined<'r, T> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        let start_position = canvas.get_start_position();
+
+        let mut region = RegionCanvas {
+            inner: canvas,
+            rect: self.rect,
+            overflowed: false,
+            start_position: RenderPosition::zero(),
+            restore: None,
+        };
+
+        region.start_position = region.to_local(start_position);
+
+        let result = self.renderable.render_into(&mut region);
+        self.overflowed.set(region.overflowed);
+
+        result
+    }
+}
+
+impl<'a, T: Tag> RegionCanvas<'a, T> {
+    /// Confines writes to `rect`, given in the coordinates of `inner`, and moves the cursor to its
+    /// top-left corner until the region is dropped.
+    pub fn new(inner: &'a mut dyn Canvas<T>, rect: Rect) -> Self {
+        let restore = inner.get_position();
+        inner.set_position(rect.position());
+
+        Self {
+            inner,
+            rect,
+            overflowed: false,
+            start_position: RenderPosition::zero(),
+            restore: Some(restore),
+        }
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    fn to_inner(&self, position: RenderPosition) -> RenderPosition {
+        RenderPosition::new(
+            self.rect.position().line() + position.line(),
+            self.rect.position().column() + position.column(),
+        )
+    }
+
+    fn to_local(&self, position: RenderPosition) -> RenderPosition {
+        RenderPosition::new(
+            position.line().saturating_sub(self.rect.position().line()),
+            position
+                .column()
+                .saturating_sub(self.rect.position().column()),
+        )
+    }
+
+    /// Whether nothing can be drawn beside this region on the lines it covers.
+    fn spans_inner_width(&self) -> bool {
+        let start = self.inner.get_start_position().column();
+
+        self.inner.available_width().is_some_and(|width| {
+            self.rect.position().column() <= start && self.rect.end_column() >= start + width
+        })
+    }
+
+    fn tab_target(&self) -> Option<usize> {
+        let column = self.get_position().column();
+        let target = column + self.inner.tab_stops().advance(column);
+
+        (target <= self.rect.size().max_column()).then_some(target)
+    }
+}
+
+impl<'a, T: Tag> Canvas<T> for RegionCanvas<'a, T> {
+    fn write(&mut self, renderable: &dyn Renderable<T>) -> Result<CanvasSummary, RenderableError> {
+        let overflowed = Cell::new(false);
+
+        let summary = self.inner.write(&Confined {
+            renderable,
+            rect: self.rect,
+            overflowed: &overflowed,
+        })?;
+
+        let summary = CanvasSummary {
+            start_position: self.to_local(summary.start_position),
+            end_position: self.to_local(summary.end_position),
+            bounds: summary
+                .bounds
+                .map(|bounds| Rect::new(self.to_local(bounds.position()), bounds.size())),
+            overflowed: summary.overflowed || overflowed.get(),
+        };
+
+        self.overflowed |= summary.overflowed;
+
+        Ok(summary)
+    }
+
+    fn set_char(&mut self, ch: char, tag: T) -> bool {
+        self.set_gph(&CharGrapheme::from(ch), tag)
+    }
+
+    fn set_gph(&mut self, v: &gph, tag: T) -> bool {
+        let position = self.inner.get_position();
+
+        if !self.can_set_gph(v) {
+            self.overflowed = true;
+            return false;
+        }
+
+        if v.as_str() == "\t" {
+            return true;
+        }
+
+        self.inner.set_position(position);
+        self.inner.set_gph(v, tag)
+    }
+
+    fn can_set_gph(&mut self, v: &gph) -> bool {
+        let position = self.inner.get_position();
+
+        if !(self.rect.position().line()..self.rect.end_line()).contains(&position.line()) {
+            return false;
+        }
+
+        if v.as_str() == "\t" {
+            return match self.tab_target() {
+                Some(column) => {
+                    self.set_column(column);
+                    true
+                }
+                None => false,
+            };
+        }
+
+        // Let the inner canvas measure the grapheme, so control characters are accounted for.
+        if !self.inner.can_set_gph(v) {
+            return false;
+        }
+
+        if self.inner.get_position().column() > self.rect.end_column() {
+            self.inner.set_position(position);
+            return false;
+        }
+
+        true
+    }
+
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        let position = self.to_inner(position);
+
+        if self.rect.contains(position) {
+            self.inner.get_gph(position)
+        } else {
+            None
+        }
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        let inner_end = self
+            .inner
+            .available_width()
+            .map(|width| self.inner.get_start_position().column() + width);
+        let end = inner_end.map_or(self.rect.end_column(), |end| {
+            end.min(self.rect.end_column())
+        });
+
+        Some(end.saturating_sub(self.rect.position().column() + self.start_position.column()))
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
+    fn get_start_position(&self) -> RenderPosition {
+        self.start_position
+    }
+
+    fn get_position(&self) -> RenderPosition {
+        self.to_local(self.inner.get_position())
+    }
+
+    fn set_position(&mut self, position: RenderPosition) -> &mut dyn Canvas<T> {
+        self.inner.set_position(self.to_inner(position));
+        self
+    }
+
+    fn set_column(&mut self, column: usize) -> &mut dyn Canvas<T> {
+        self.inner
+            .set_column(self.rect.position().column() + column);
+        self
+    }
+
+    fn set_line(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line(self.rect.position().line() + line);
+        self
+    }
+
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        if self.spans_inner_width() {
+            let line = line.min(self.rect.size().lines());
+            self.inner
+                .set_line_watermark(self.rect.position().line() + line);
+        }
+
+        self
+    }
+
+    fn cursor_down(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down();
+        self
+    }
+
+    fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down_by(count);
+        self
+    }
+
+    fn try_cursor_up(&mut self) -> bool {
+        self.try_cursor_up_by(1)
+    }
+
+    fn try_cursor_up_by(&mut self, count: usize) -> bool {
+        if self.get_position().line() < count {
+            return false;
+        }
+
+        self.inner.cursor_up_by(count);
+        true
+    }
+
+    fn cursor_up(&mut self) -> &mut dyn Canvas<T> {
+        self.cursor_up_by(1)
+    }
+
+    fn cursor_up_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        assert!(
+            self.try_cursor_up_by(count),
+            "Expected to be able to move the cursor up"
+        );
+        self
+    }
+
+    fn try_cursor_left(&mut self) -> bool {
+        self.try_cursor_left_by(1)
+    }
+
+    fn try_cursor_left_by(&mut self, count: usize) -> bool {
+        if self.get_position().column() < count {
+            return false;
+        }
+
+        self.inner.cursor_left_by(count);
+        true
+    }
+
+    fn cursor_left(&mut self) -> &mut dyn Canvas<T> {
+        self.cursor_left_by(1)
+    }
+
+    fn cursor_left_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        assert!(
+            self.try_cursor_left_by(count),
+            "Expected to be able to move the cursor left"
+        );
+        self
+    }
+
+    fn cursor_right(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right();
+        self
+    }
+
+    fn cursor_right_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right_by(count);
+        self
+    }
+}
+
+impl<'a, T: Tag> Drop for RegionCanvas<'a, T> {
+    fn drop(&mut self) {
+        if let Some(position) = self.restore {
+            self.inner.set_position(position);
+        }
+    }
+}
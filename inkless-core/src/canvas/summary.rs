@@ -1,13 +1,25 @@
-use crate::render_position::RenderPosition;
+use crate::render_position::{Rect, RenderPosition};
 
 #[derive(Clone, Copy)]
 pub struct CanvasSummary {
     pub start_position: RenderPosition,
     pub end_position: RenderPosition,
+
+    /// The smallest rectangle containing every cell written, or `None` if nothing was written.
+    pub bounds: Option<Rect>,
+
+    /// Whether any grapheme was rejected for not fitting, including ones a wrapping renderable then
+    /// moved to the next line.
+    pub overflowed: bool,
 }
 
 impl CanvasSummary {
     pub fn get_line_height(&self) -> usize {
         1 + (self.end_position.line() - self.start_position.line())
     }
+
+    /// The column just past the rightmost cell written, or `None` if nothing was written.
+    pub fn max_column(&self) -> Option<usize> {
+        self.bounds.map(|bounds| bounds.end_column())
+    }
 }
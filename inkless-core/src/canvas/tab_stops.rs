@@ -0,0 +1,35 @@
+/// Where a `\t` written to a canvas moves the cursor to.
+///
+/// Stops are columns counted from the canvas's start column, so a renderable lines up its own tabs
+/// no matter where it is drawn.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TabStops {
+    /// A stop every `n` columns. `Every(0)` has no stops.
+    Every(usize),
+    /// Stops at each listed column, in ascending order.
+    List(&'static [usize]),
+}
+
+impl Default for TabStops {
+    fn default() -> Self {
+        TabStops::Every(8)
+    }
+}
+
+impl TabStops {
+    /// Returns the first stop strictly after `column`, or `None` if there are no more stops.
+    pub fn next_stop(&self, column: usize) -> Option<usize> {
+        match *self {
+            TabStops::Every(0) => None,
+            TabStops::Every(n) => Some((column / n + 1) * n),
+            TabStops::List(stops) => stops.iter().copied().find(|&stop| stop > column),
+        }
+    }
+
+    /// Returns how many columns a tab written at `column` advances the cursor by.
+    ///
+    /// Past the last stop, a tab advances by a single column.
+    pub fn advance(&self, column: usize) -> usize {
+        self.next_stop(column).map_or(1, |stop| stop - column)
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::Display;
+
+use crate::render_position::RenderPosition;
+
+/// What a renderable is warning about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// Content was cut off to fit the available space.
+    Truncated,
+    /// A grapheme could not be stored or shown as written, and was replaced.
+    UnrepresentableGrapheme,
+    /// A colour could not be shown as written, and a less precise one was used instead.
+    ColorDowngraded,
+    /// A warning specific to the renderable that reported it.
+    Other(&'static str),
+}
+
+/// A warning reported while rendering, which did not stop the render.
+#[derive(Clone, Copy, Debug)]
+pub struct Diagnostic {
+    pub kind: DiagnosticKind,
+    /// Where the cursor was when the warning was reported, in buffer coordinates.
+    pub position: RenderPosition,
+}
+
+impl Diagnostic {
+    pub fn new(kind: DiagnosticKind, position: RenderPosition) -> Self {
+        Self { kind, position }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.kind {
+            DiagnosticKind::Truncated => f.write_str("content was truncated")?,
+            DiagnosticKind::UnrepresentableGrapheme => {
+                f.write_str("a grapheme could not be represented and was replaced")?
+            }
+            DiagnosticKind::ColorDowngraded => {
+                f.write_str("a colour was downgraded to one the output supports")?
+            }
+            DiagnosticKind::Other(message) => f.write_str(message)?,
+        }
+
+        write!(
+            f,
+            " at line {}, column {}",
+            self.position.line(),
+            self.position.column()
+        )
+    }
+}
+
+/// The number of diagnostics kept without `alloc`. Any reported after that are only counted.
+#[cfg(not(feature = "alloc"))]
+pub const INLINE_DIAGNOSTICS: usize = 8;
+
+/// The diagnostics collected during a render, in the order they were reported.
+#[derive(Clone, Debug, Default)]
+pub struct Diagnostics {
+    #[cfg(feature = "alloc")]
+    entries: Vec<Diagnostic>,
+    #[cfg(not(feature = "alloc"))]
+    entries: [Option<Diagnostic>; INLINE_DIAGNOSTICS],
+    reported: usize,
+}
+
+impl Diagnostics {
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        #[cfg(feature = "alloc")]
+        self.entries.push(diagnostic);
+
+        #[cfg(not(feature = "alloc"))]
+        if let Some(entry) = self.entries.get_mut(self.reported) {
+            *entry = Some(diagnostic);
+        }
+
+        self.reported += 1;
+    }
+
+    /// Moves every diagnostic kept by `other` to the end of this collection.
+    pub fn append(&mut self, other: Diagnostics) {
+        let dropped = other.dropped();
+
+        for diagnostic in other.iter() {
+            self.push(*diagnostic);
+        }
+
+        self.reported += dropped;
+    }
+
+    /// Returns the kept diagnostics.
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        #[cfg(feature = "alloc")]
+        return self.entries.iter();
+
+        #[cfg(not(feature = "alloc"))]
+        return self.entries.iter().flatten();
+    }
+
+    /// Returns the number of diagnostics reported, including any that were not kept.
+    pub fn len(&self) -> usize {
+        self.reported
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reported == 0
+    }
+
+    /// Returns the number of diagnostics that were reported but not kept, which is always 0 with
+    /// `alloc`.
+    pub fn dropped(&self) -> usize {
+        self.reported - self.iter().count()
+    }
+}
@@ -1,8 +1,9 @@
+use alloc::string::String;
 use core::ops::Deref;
 
 use crate::grapheme::gph;
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Grapheme(String);
 
 impl From<&gph> for Grapheme {
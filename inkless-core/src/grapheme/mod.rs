@@ -1,10 +1,11 @@
 use unicode_segmentation::UnicodeSegmentation;
 use unicode_width::UnicodeWidthStr;
 
-use crate::canvas::AmbiguityPolicy;
+use crate::grapheme::width::WidthProvider;
 
 pub mod char;
 pub mod r#static;
+pub mod width;
 
 #[cfg(feature = "alloc")]
 pub mod grapheme;
@@ -47,11 +48,9 @@ impl gph {
         self.0.len()
     }
 
-    pub fn width(&self, policy: AmbiguityPolicy) -> usize {
-        match policy {
-            AmbiguityPolicy::Standard => self.width_normal(),
-            AmbiguityPolicy::Wide => self.width_cjk(),
-        }
+    /// Returns the number of columns this grapheme occupies according to `provider`.
+    pub fn width<P: WidthProvider + ?Sized>(&self, provider: &P) -> usize {
+        provider.width(self)
     }
 
     pub fn width_normal(&self) -> usize {
@@ -0,0 +1,128 @@
+use core::fmt::Debug;
+
+use unicode_width::UnicodeWidthChar;
+
+use crate::{canvas::AmbiguityPolicy, grapheme::gph};
+
+/// Decides how many columns a grapheme occupies.
+///
+/// Terminals disagree on the width of emoji sequences, flags and ambiguous-width characters, so
+/// buffers measure graphemes through a provider chosen to match the terminal being rendered to.
+pub trait WidthProvider {
+    fn width(&self, grapheme: &gph) -> usize;
+}
+
+impl<'a> Debug for dyn WidthProvider + 'a {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("dyn WidthProvider")
+    }
+}
+
+impl<P: WidthProvider + ?Sized> WidthProvider for &P {
+    fn width(&self, grapheme: &gph) -> usize {
+        (**self).width(grapheme)
+    }
+}
+
+/// Measures graphemes with `unicode-width`, treating ambiguous-width characters as the policy says.
+impl WidthProvider for AmbiguityPolicy {
+    fn width(&self, grapheme: &gph) -> usize {
+        match self {
+            AmbiguityPolicy::Standard => grapheme.width_normal(),
+            AmbiguityPolicy::Wide => grapheme.width_cjk(),
+        }
+    }
+}
+
+/// Measures graphemes the way `wcswidth` does: the sum of each code point's width.
+///
+/// Terminals built on `wcwidth` draw emoji ZWJ sequences and flags as their individual parts, so a
+/// family emoji is 6 columns wide and a flag is 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Wcwidth(pub AmbiguityPolicy);
+
+impl WidthProvider for Wcwidth {
+    fn width(&self, grapheme: &gph) -> usize {
+        grapheme
+            .as_str()
+            .chars()
+            .map(|c| match self.0 {
+                AmbiguityPolicy::Standard => c.width(),
+                AmbiguityPolicy::Wide => c.width_cjk(),
+            })
+            .map(|width| width.unwrap_or(0))
+            .sum()
+    }
+}
+
+/// Measures every emoji sequence as exactly 2 columns, and everything else with `unicode-width`.
+///
+/// A grapheme counts as an emoji sequence when it contains a presentation selector (U+FE0F), a
+/// zero-width joiner, a skin tone modifier or a keycap, or when it is a regional indicator flag.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EmojiWide(pub AmbiguityPolicy);
+
+impl EmojiWide {
+    fn is_emoji_sequence(grapheme: &gph) -> bool {
+        let mut chars = grapheme.as_str().chars();
+
+        if chars
+            .next()
+            .is_some_and(|c| ('\u{1F1E6}'..='\u{1F1FF}').contains(&c))
+        {
+            return true;
+        }
+
+        chars.any(|c| {
+            matches!(
+                c,
+                '\u{FE0F}' | '\u{200D}' | '\u{20E3}' | '\u{1F3FB}'..='\u{1F3FF}'
+            )
+        })
+    }
+}
+
+impl WidthProvider for EmojiWide {
+    fn width(&self, grapheme: &gph) -> usize {
+        if Self::is_emoji_sequence(grapheme) {
+            2
+        } else {
+            self.0.width(grapheme)
+        }
+    }
+}
+
+impl AmbiguityPolicy {
+    /// Picks the ambiguous-width policy for a POSIX locale name such as `ja_JP.UTF-8`.
+    ///
+    /// Chinese, Japanese and Korean locales get `Wide`, since terminals in those locales usually
+    /// draw ambiguous-width characters as two columns; everything else gets `Standard`.
+    pub fn from_locale(locale: &str) -> Self {
+        let language = locale
+            .split(['_', '-', '.', '@'])
+            .next()
+            .unwrap_or_default();
+
+        if ["zh", "ja", "ko"]
+            .iter()
+            .any(|cjk| language.eq_ignore_ascii_case(cjk))
+        {
+            AmbiguityPolicy::Wide
+        } else {
+            AmbiguityPolicy::Standard
+        }
+    }
+
+    /// Picks the ambiguous-width policy from the first of `LC_ALL`, `LC_CTYPE` and `LANG` that is
+    /// set, falling back to `Standard`.
+    #[cfg(feature = "std")]
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_CTYPE", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(AmbiguityPolicy::Standard, |locale| {
+                Self::from_locale(&locale)
+            })
+    }
+}
@@ -6,7 +6,9 @@ extern crate alloc;
 pub mod buffer;
 pub mod builtins;
 pub mod canvas;
+pub mod diagnostics;
 pub mod grapheme;
+pub mod measure;
 pub mod render_position;
 pub mod renderable;
 pub mod tag;
@@ -0,0 +1,69 @@
+use crate::{
+    buffer::null::NullRenderBuffer,
+    canvas::ext::RenderBufferCanvasExt,
+    grapheme::width::WidthProvider,
+    render_position::{RenderPosition, Size},
+    renderable::{Renderable, RenderableError},
+    tag::Tag,
+};
+
+fn render_null<'w, T: Tag, R: Renderable<T>>(
+    renderable: &R,
+    width: Option<usize>,
+    width_provider: &'w dyn WidthProvider,
+) -> Result<NullRenderBuffer<'w, T>, RenderableError> {
+    let mut buffer = NullRenderBuffer::new(width, width_provider);
+    let mut canvas = buffer.canvas_at(RenderPosition::zero());
+
+    renderable.render_into(&mut canvas)?;
+
+    Ok(buffer)
+}
+
+/// Computes the size `renderable` occupies when rendered at `width`, without producing any output.
+pub fn measure<T: Tag, R: Renderable<T>>(
+    renderable: R,
+    width: usize,
+    width_provider: &dyn WidthProvider,
+) -> Result<Size, RenderableError> {
+    render_null(&renderable, Some(width), width_provider).map(|buffer| buffer.size())
+}
+
+/// Returns the width `renderable` occupies when it is given as much room as it wants.
+pub fn max_content_width<T: Tag, R: Renderable<T>>(
+    renderable: R,
+    width_provider: &dyn WidthProvider,
+) -> Result<usize, RenderableError> {
+    render_null(&renderable, None, width_provider).map(|buffer| buffer.size().max_column())
+}
+
+/// Returns the narrowest width at which `renderable` still renders all of its content.
+///
+/// A width is considered wide enough when rendering at it succeeds and writes as many cells as
+/// rendering at the max-content width does. This assumes that a renderable which fits at some width
+/// also fits at every wider one.
+pub fn min_content_width<T: Tag, R: Renderable<T>>(
+    renderable: R,
+    width_provider: &dyn WidthProvider,
+) -> Result<usize, RenderableError> {
+    let unbounded = render_null(&renderable, None, width_provider)?;
+    let cells = unbounded.cells_set();
+
+    let mut low = 0;
+    let mut high = unbounded.size().max_column();
+
+    while low < high {
+        let width = low + (high - low) / 2;
+
+        let fits = render_null(&renderable, Some(width), width_provider)
+            .is_ok_and(|buffer| buffer.cells_set() == cells);
+
+        if fits {
+            high = width;
+        } else {
+            low = width + 1;
+        }
+    }
+
+    Ok(low)
+}
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct RenderPosition {
     line: usize,
     column: usize,
@@ -49,3 +49,81 @@ impl RenderPosition {
         self.column
     }
 }
+
+/// The extent of a rendered area.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Size {
+    lines: usize,
+    max_column: usize,
+}
+
+impl Size {
+    pub fn new(lines: usize, max_column: usize) -> Self {
+        Self { lines, max_column }
+    }
+
+    pub fn zero() -> Self {
+        Self::new(0, 0)
+    }
+
+    /// The number of lines, counting from line 0 to the lowest line written.
+    pub fn lines(&self) -> usize {
+        self.lines
+    }
+
+    /// The column just past the rightmost written cell, i.e. the width the content occupies.
+    pub fn max_column(&self) -> usize {
+        self.max_column
+    }
+}
+
+/// A rectangle of cells, `size.lines()` tall and `size.max_column()` wide, with its top-left
+/// corner at `position`.
+#[derive(Debug, Clone, Copy)]
+pub struct Rect {
+    position: RenderPosition,
+    size: Size,
+}
+
+impl Rect {
+    pub fn new(position: RenderPosition, size: Size) -> Self {
+        Self { position, size }
+    }
+
+    pub fn position(&self) -> RenderPosition {
+        self.position
+    }
+
+    pub fn size(&self) -> Size {
+        self.size
+    }
+
+    /// The line just below the bottom edge.
+    pub fn end_line(&self) -> usize {
+        self.position.line() + self.size.lines()
+    }
+
+    /// The column just past the right edge.
+    pub fn end_column(&self) -> usize {
+        self.position.column() + self.size.max_column()
+    }
+
+    pub fn contains(&self, position: RenderPosition) -> bool {
+        (self.position.line()..self.end_line()).contains(&position.line())
+            && (self.position.column()..self.end_column()).contains(&position.column())
+    }
+
+    /// Returns the smallest rectangle containing both `self` and `other`.
+    pub fn union(&self, other: Rect) -> Rect {
+        let line = self.position.line().min(other.position.line());
+        let column = self.position.column().min(other.position.column());
+
+        Rect::new(
+            RenderPosition::new(line, column),
+            Size::new(
+                self.end_line().max(other.end_line()) - line,
+                self.end_column().max(other.end_column()) - column,
+            ),
+        )
+    }
+}
@@ -1,3 +1,5 @@
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
 use core::{
     error::Error,
     fmt::{Debug, Display},
@@ -5,6 +7,7 @@ use core::{
 
 use crate::{canvas::Canvas, tag::Tag};
 
+#[deprecated(note = "wrap the error with `RenderableError::new` or `RenderableError::from_static`")]
 pub trait RenderContext {
     fn handle_error<'b>(&'b mut self, error: &'b dyn core::error::Error);
 }
@@ -25,23 +28,21 @@ impl<'a, T: Tag, O: Renderable<T>> Renderable<T> for &'a O {
     }
 }
 
-pub struct RenderableError {
-    provider: for<'a> fn(&'a mut dyn RenderContext) -> (),
-}
-
-impl RenderableError {
-    pub fn of(provider: for<'a> fn(&'a mut dyn RenderContext) -> ()) -> Self {
-        Self { provider }
-    }
+enum Repr {
+    Static(&'static (dyn Error + Send + Sync)),
+    #[cfg(feature = "alloc")]
+    Boxed(Box<dyn Error + Send + Sync>),
+    Provided(ProvidedError),
 }
 
-impl From<for<'a> fn(&'a mut dyn RenderContext) -> ()> for RenderableError {
-    fn from(provider: for<'a> fn(&'a mut dyn RenderContext) -> ()) -> Self {
-        Self { provider }
-    }
+/// An error that is only reachable by calling `provider` with a `RenderContext`.
+#[allow(deprecated)]
+struct ProvidedError {
+    provider: for<'a> fn(&'a mut dyn RenderContext) -> (),
 }
 
-impl Debug for RenderableError {
+#[allow(deprecated)]
+impl Debug for ProvidedError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         struct DebugHandler<'a, 'b> {
             formatter: &'a mut core::fmt::Formatter<'b>,
@@ -71,7 +72,8 @@ impl Debug for RenderableError {
     }
 }
 
-impl Display for RenderableError {
+#[allow(deprecated)]
+impl Display for ProvidedError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         struct DisplayHandler<'a, 'b> {
             formatter: &'a mut core::fmt::Formatter<'b>,
@@ -101,4 +103,82 @@ impl Display for RenderableError {
     }
 }
 
-impl Error for RenderableError {}
+impl Error for ProvidedError {}
+
+/// The error a `Renderable` fails with.
+///
+/// Wraps any `Error + Send + Sync`. With `alloc` the error is owned and can carry runtime data;
+/// without it, only `'static` errors can be wrapped.
+///
+/// `Display` and `source` forward to the wrapped error, and `downcast_ref` recovers it.
+pub struct RenderableError {
+    repr: Repr,
+}
+
+impl RenderableError {
+    /// Wraps an owned error.
+    #[cfg(feature = "alloc")]
+    pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Self {
+        Self {
+            repr: Repr::Boxed(Box::new(error)),
+        }
+    }
+
+    /// Wraps an error that lives for the whole program, without allocating.
+    pub fn from_static(error: &'static (dyn Error + Send + Sync)) -> Self {
+        Self {
+            repr: Repr::Static(error),
+        }
+    }
+
+    /// Wraps an error handed to a `RenderContext` by `provider`.
+    #[deprecated(note = "use `RenderableError::new` or `RenderableError::from_static`")]
+    #[allow(deprecated)]
+    pub fn of(provider: for<'a> fn(&'a mut dyn RenderContext) -> ()) -> Self {
+        Self {
+            repr: Repr::Provided(ProvidedError { provider }),
+        }
+    }
+
+    /// Returns the wrapped error.
+    pub fn get_ref(&self) -> &(dyn Error + Send + Sync + 'static) {
+        match &self.repr {
+            Repr::Static(error) => *error,
+            #[cfg(feature = "alloc")]
+            Repr::Boxed(error) => error.as_ref(),
+            Repr::Provided(error) => error,
+        }
+    }
+
+    /// Returns the wrapped error if it is an `E`.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.get_ref().downcast_ref()
+    }
+}
+
+#[allow(deprecated)]
+impl From<for<'a> fn(&'a mut dyn RenderContext) -> ()> for RenderableError {
+    fn from(provider: for<'a> fn(&'a mut dyn RenderContext) -> ()) -> Self {
+        Self {
+            repr: Repr::Provided(ProvidedError { provider }),
+        }
+    }
+}
+
+impl Debug for RenderableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Debug::fmt(self.get_ref(), f)
+    }
+}
+
+impl Display for RenderableError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        Display::fmt(self.get_ref(), f)
+    }
+}
+
+impl Error for RenderableError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.get_ref().source()
+    }
+}
@@ -1,8 +1,36 @@
 pub mod sink;
 pub mod untagged;
 
-pub trait Tag {}
+pub trait Tag {
+    /// Combines this tag with the tag of the renderable it is written within, for
+    /// `Canvas::write_within`.
+    ///
+    /// The default keeps `self` and ignores the parent. Style tags override this so that fields
+    /// left unset inherit from `parent`.
+    fn merge(self, parent: &Self) -> Self
+    where
+        Self: Sized,
+    {
+        let _ = parent;
+        self
+    }
+}
+
+/// A tag that names the semantic role of the text it marks, so themes loaded at runtime can style
+/// it by name.
+///
+/// Names are dot-separated paths from the general to the specific, such as `error` or
+/// `number.separator`.
+pub trait NamedTag: Tag {
+    fn name(&self) -> &str;
+}
 
 impl<T: Tag + ?Sized> Tag for &T {}
 
+impl<T: NamedTag + ?Sized> NamedTag for &T {
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+}
+
 impl<T: Tag> Tag for Option<T> {}
@@ -1,11 +1,15 @@
-use core::marker::PhantomData;
+use core::{
+    any::{Any, TypeId},
+    marker::PhantomData,
+};
 
-use crate::theme::Theme;
+use crate::theme::{RuntimeTheme, Theme};
 use crate::{canvas::Canvas, tag::Tag};
 
 use crate::{
-    canvas::summary::CanvasSummary,
-    grapheme::gph,
+    canvas::{adapter::AdaptedRenderable, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
     render_position::RenderPosition,
     renderable::{Renderable, RenderableError},
 };
@@ -26,14 +30,29 @@ where
         &'b mut self,
         renderable: &dyn Renderable<Ot>,
     ) -> Result<CanvasSummary, RenderableError> {
-        let start = self.get_position();
-        renderable.render_into(self)?;
-        let end = self.get_position();
+        self.inner
+            .write(&AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut ThemeCanvas::<_, Th> {
+                    inner: canvas,
+                    theme: PhantomData,
+                })
+            }))
+    }
 
-        Ok(CanvasSummary {
-            start_position: start,
-            end_position: end,
-        })
+    fn write_within(
+        &mut self,
+        parent: Ot,
+        renderable: &dyn Renderable<Ot>,
+    ) -> Result<CanvasSummary, RenderableError> {
+        self.inner.write_within(
+            Th::translate(parent),
+            &AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut ThemeCanvas::<_, Th> {
+                    inner: canvas,
+                    theme: PhantomData,
+                })
+            }),
+        )
     }
 
     fn set_char(&mut self, ch: char, tag: Ot) -> bool {
@@ -51,6 +70,233 @@ where
         self.inner.can_set_gph(v)
     }
 
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<Ot> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
+    fn get_start_position(&self) -> RenderPosition {
+        self.inner.get_start_position()
+    }
+
+    fn get_position(&self) -> RenderPosition {
+        self.inner.get_position()
+    }
+
+    fn set_position(&mut self, position: RenderPosition) -> &mut dyn Canvas<Ot> {
+        self.inner.set_position(position);
+        self
+    }
+
+    fn set_column(&mut self, column: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.set_column(column);
+        self
+    }
+
+    fn set_line(&mut self, line: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.set_line(line);
+        self
+    }
+
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
+    fn cursor_down(&mut self) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_down();
+        self
+    }
+
+    fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_down_by(count);
+        self
+    }
+
+    fn try_cursor_up(&mut self) -> bool {
+        self.inner.try_cursor_up()
+    }
+
+    fn try_cursor_up_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_up_by(count)
+    }
+
+    fn cursor_up(&mut self) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_up();
+        self
+    }
+
+    fn cursor_up_by(&mut self, count: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_up_by(count);
+        self
+    }
+
+    fn try_cursor_left(&mut self) -> bool {
+        self.inner.try_cursor_left()
+    }
+
+    fn try_cursor_left_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_left_by(count)
+    }
+
+    fn cursor_left(&mut self) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_left();
+        self
+    }
+
+    fn cursor_left_by(&mut self, count: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_left_by(count);
+        self
+    }
+
+    fn cursor_right(&mut self) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_right();
+        self
+    }
+
+    fn cursor_right_by(&mut self, count: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.cursor_right_by(count);
+        self
+    }
+}
+
+/// A canvas that maps tags through a borrowed `RuntimeTheme<Ot>`, for themes built at runtime.
+///
+/// This is the `&self` counterpart to `ThemeCanvas`, which stays zero-sized for static themes. A
+/// static theme can still be used here through `StaticTheme`.
+pub struct RuntimeThemeCanvas<'a, 't, T: Tag, Th: ?Sized> {
+    pub(crate) inner: &'a mut dyn Canvas<T>,
+    pub(crate) theme: &'t Th,
+}
+
+impl<'a, 't, T: Tag, Th: ?Sized> RuntimeThemeCanvas<'a, 't, T, Th> {
+    pub fn new(inner: &'a mut dyn Canvas<T>, theme: &'t Th) -> Self {
+        Self { inner, theme }
+    }
+}
+
+/// Renders a renderable into a `RuntimeThemeCanvas` around the child canvas it is given.
+struct Themed<'r, 't, Ot: Tag, Th: ?Sized> {
+    renderable: &'r dyn Renderable<Ot>,
+    theme: &'t Th,
+}
+
+impl<'r, 't, Ot, Th> Renderable<Th::Result> for Themed<'r, 't, Ot, Th>
+where
+    Ot: Tag,
+    Th: RuntimeTheme<Ot> + ?Sized,
+{
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<Th::Result>,
+    ) -> Result<(), RenderableError> {
+        self.renderable
+            .render_into(&mut RuntimeThemeCanvas::new(canvas, self.theme))
+    }
+}
+
+impl<'a, 't, Ot, Th> Canvas<Ot> for RuntimeThemeCanvas<'a, 't, Th::Result, Th>
+where
+    Ot: Tag,
+    Th: RuntimeTheme<Ot> + ?Sized,
+{
+    fn write(&mut self, renderable: &dyn Renderable<Ot>) -> Result<CanvasSummary, RenderableError> {
+        self.inner.write(&Themed {
+            renderable,
+            theme: self.theme,
+        })
+    }
+
+    fn write_within(
+        &mut self,
+        parent: Ot,
+        renderable: &dyn Renderable<Ot>,
+    ) -> Result<CanvasSummary, RenderableError> {
+        self.inner.write_within(
+            self.theme.translate(parent),
+            &Themed {
+                renderable,
+                theme: self.theme,
+            },
+        )
+    }
+
+    fn set_char(&mut self, ch: char, tag: Ot) -> bool {
+        let themed = self.theme.translate(tag);
+        self.inner.set_char(ch, themed)
+    }
+
+    fn set_gph(&mut self, v: &gph, tag: Ot) -> bool {
+        let themed = self.theme.translate(tag);
+        self.inner.set_gph(v, themed)
+    }
+
+    fn can_set_gph(&mut self, v: &gph) -> bool {
+        // Width is independent of tag, so just forward.
+        self.inner.can_set_gph(v)
+    }
+
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<Ot> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
     fn get_start_position(&self) -> RenderPosition {
         self.inner.get_start_position()
     }
@@ -74,6 +320,11 @@ where
         self
     }
 
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<Ot> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
     fn cursor_down(&mut self) -> &mut dyn Canvas<Ot> {
         self.inner.cursor_down();
         self
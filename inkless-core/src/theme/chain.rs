@@ -0,0 +1,35 @@
+use crate::{
+    tag::Tag,
+    theme::{RuntimeTheme, Theme},
+};
+
+/// Translates through `A`, then translates the result through `B`.
+///
+/// Useful for layering a product theme that produces semantic tags on top of a base theme that
+/// styles them. Static themes only use the type; runtime themes are combined with `new`.
+pub struct Chain<A, B> {
+    first: A,
+    then: B,
+}
+
+impl<A, B> Chain<A, B> {
+    pub fn new(first: A, then: B) -> Self {
+        Self { first, then }
+    }
+}
+
+impl<T: Tag, A: Theme<T>, B: Theme<A::Result>> Theme<T> for Chain<A, B> {
+    type Result = B::Result;
+
+    fn translate(from: T) -> Self::Result {
+        B::translate(A::translate(from))
+    }
+}
+
+impl<T: Tag, A: RuntimeTheme<T>, B: RuntimeTheme<A::Result>> RuntimeTheme<T> for Chain<A, B> {
+    type Result = B::Result;
+
+    fn translate(&self, from: T) -> Self::Result {
+        self.then.translate(self.first.translate(from))
+    }
+}
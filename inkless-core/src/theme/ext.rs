@@ -4,7 +4,13 @@ use crate::{
     canvas::Canvas,
     renderable::Renderable,
     tag::Tag,
-    theme::{Theme, canvas::ThemeCanvas},
+    theme::{
+        RuntimeTheme, Theme,
+        canvas::{RuntimeThemeCanvas, ThemeCanvas},
+        chain::Chain,
+        fallback::Fallback,
+        map_tag::MapTag,
+    },
 };
 
 #[repr(transparent)]
@@ -13,6 +19,75 @@ pub struct ThemedRenderable<Ot: Tag, Th: Theme<Ot>, R: ?Sized + Renderable<Ot>>(
     R,
 );
 
+impl<Ot: Tag, Th: Theme<Ot>, R: Renderable<Ot>> ThemedRenderable<Ot, Th, R> {
+    /// Translates the result of this theme through `Next` as well.
+    pub fn chain<Next: Theme<Th::Result>>(self) -> ThemedRenderable<Ot, Chain<Th, Next>, R> {
+        ThemedRenderable(PhantomData, self.1)
+    }
+
+    /// Uses `Base` for the tags this theme translates to `None`.
+    pub fn fallback<Base>(self) -> ThemedRenderable<Ot, Fallback<Th, Base>, R>
+    where
+        Fallback<Th, Base>: Theme<Ot>,
+    {
+        ThemedRenderable(PhantomData, self.1)
+    }
+
+    /// Converts the result of this theme into `U`, then translates it through `Next`.
+    pub fn map_tag<U: Tag, Next: Theme<U>>(
+        self,
+    ) -> ThemedRenderable<Ot, Chain<Th, MapTag<U, Next>>, R>
+    where
+        Th::Result: Into<U>,
+    {
+        ThemedRenderable(PhantomData, self.1)
+    }
+}
+
+/// A renderable whose tags are mapped through a `RuntimeTheme`, usually a borrowed one.
+pub struct RuntimeThemedRenderable<Ot: Tag, Th, R: ?Sized> {
+    theme: Th,
+    _tag: PhantomData<Ot>,
+    inner: R,
+}
+
+impl<Ot: Tag, Th: RuntimeTheme<Ot>, R: Renderable<Ot>> RuntimeThemedRenderable<Ot, Th, R> {
+    /// Translates the result of this theme through `next` as well.
+    pub fn chain<Next: RuntimeTheme<Th::Result>>(
+        self,
+        next: Next,
+    ) -> RuntimeThemedRenderable<Ot, Chain<Th, Next>, R> {
+        RuntimeThemedRenderable {
+            theme: Chain::new(self.theme, next),
+            _tag: PhantomData,
+            inner: self.inner,
+        }
+    }
+
+    /// Uses `base` for the tags this theme translates to `None`.
+    pub fn fallback<Base>(self, base: Base) -> RuntimeThemedRenderable<Ot, Fallback<Th, Base>, R>
+    where
+        Fallback<Th, Base>: RuntimeTheme<Ot>,
+    {
+        RuntimeThemedRenderable {
+            theme: Fallback::new(self.theme, base),
+            _tag: PhantomData,
+            inner: self.inner,
+        }
+    }
+
+    /// Converts the result of this theme into `U`, then translates it through `next`.
+    pub fn map_tag<U: Tag, Next: RuntimeTheme<U>>(
+        self,
+        next: Next,
+    ) -> RuntimeThemedRenderable<Ot, Chain<Th, MapTag<U, Next>>, R>
+    where
+        Th::Result: Into<U>,
+    {
+        self.chain(MapTag::new(next))
+    }
+}
+
 pub trait RenderableThemeExt<Ot: Tag>: Renderable<Ot> {
     fn as_theme<'a, Th: Theme<Ot>>(&'a self) -> &'a ThemedRenderable<Ot, Th, Self>
     where
@@ -21,6 +96,20 @@ pub trait RenderableThemeExt<Ot: Tag>: Renderable<Ot> {
     fn with_theme<'a, Th: Theme<Ot>>(self) -> ThemedRenderable<Ot, Th, Self>
     where
         Self: Renderable<Ot>;
+
+    fn as_runtime_theme<'a, 't, Th: RuntimeTheme<Ot> + ?Sized>(
+        &'a self,
+        theme: &'t Th,
+    ) -> RuntimeThemedRenderable<Ot, &'t Th, &'a Self>
+    where
+        Self: Renderable<Ot>;
+
+    fn with_runtime_theme<Th: RuntimeTheme<Ot> + ?Sized>(
+        self,
+        theme: &Th,
+    ) -> RuntimeThemedRenderable<Ot, &Th, Self>
+    where
+        Self: Renderable<Ot>;
 }
 
 impl<T: Tag, R: Renderable<T>> RenderableThemeExt<T> for R {
@@ -41,6 +130,30 @@ impl<T: Tag, R: Renderable<T>> RenderableThemeExt<T> for R {
     {
         ThemedRenderable(Default::default(), self)
     }
+
+    fn as_runtime_theme<'a, 't, Th: RuntimeTheme<T> + ?Sized>(
+        &'a self,
+        theme: &'t Th,
+    ) -> RuntimeThemedRenderable<T, &'t Th, &'a Self>
+    where
+        Self: Renderable<T>,
+    {
+        self.with_runtime_theme(theme)
+    }
+
+    fn with_runtime_theme<Th: RuntimeTheme<T> + ?Sized>(
+        self,
+        theme: &Th,
+    ) -> RuntimeThemedRenderable<T, &Th, Self>
+    where
+        Self: Renderable<T>,
+    {
+        RuntimeThemedRenderable {
+            theme,
+            _tag: PhantomData,
+            inner: self,
+        }
+    }
 }
 
 impl<Tt: Tag, Ot: Tag, Th: Theme<Ot, Result = Tt>, R> Renderable<Tt> for ThemedRenderable<Ot, Th, R>
@@ -59,3 +172,18 @@ where
         self.1.render_into(&mut c)
     }
 }
+
+impl<Ot, Th, R> Renderable<Th::Result> for RuntimeThemedRenderable<Ot, Th, R>
+where
+    Ot: Tag,
+    Th: RuntimeTheme<Ot>,
+    R: ?Sized + Renderable<Ot>,
+{
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<Th::Result>,
+    ) -> Result<(), crate::renderable::RenderableError> {
+        self.inner
+            .render_into(&mut RuntimeThemeCanvas::new(canvas, &self.theme))
+    }
+}
@@ -0,0 +1,53 @@
+use crate::{
+    tag::Tag,
+    theme::{RuntimeTheme, Theme},
+};
+
+/// Translates through `A`, falling back to `B` for the tags `A` translates to `None`.
+///
+/// `A` is a partial theme: it only overrides the tags it cares about, and leaves the rest to `B`.
+/// Static themes only use the type; runtime themes are combined with `new`.
+pub struct Fallback<A, B> {
+    overrides: A,
+    base: B,
+}
+
+impl<A, B> Fallback<A, B> {
+    pub fn new(overrides: A, base: B) -> Self {
+        Self { overrides, base }
+    }
+}
+
+impl<T, R, A, B> Theme<T> for Fallback<A, B>
+where
+    T: Tag + Clone,
+    R: Tag,
+    A: Theme<T, Result = Option<R>>,
+    B: Theme<T, Result = R>,
+{
+    type Result = R;
+
+    fn translate(from: T) -> Self::Result {
+        match A::translate(from.clone()) {
+            Some(result) => result,
+            None => B::translate(from),
+        }
+    }
+}
+
+impl<T, R, A, B> RuntimeTheme<T> for Fallback<A, B>
+where
+    T: Tag + Clone,
+    R: Tag,
+    A: RuntimeTheme<T, Result = Option<R>>,
+    B: RuntimeTheme<T, Result = R>,
+{
+    type Result = R;
+
+    fn translate(&self, from: T) -> Self::Result {
+        match self.overrides.translate(from.clone()) {
+            Some(result) => result,
+            None => self.base.translate(from),
+        }
+    }
+}
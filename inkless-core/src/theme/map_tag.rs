@@ -0,0 +1,40 @@
+use core::marker::PhantomData;
+
+use crate::{
+    tag::Tag,
+    theme::{RuntimeTheme, Theme},
+};
+
+/// Converts tags into `U` with `Into`, then translates them through `Th`.
+///
+/// Lets a theme written for one tag type style another, given a `From` impl between them. Static
+/// themes only use the type; runtime themes are wrapped with `new`.
+pub struct MapTag<U, Th> {
+    theme: Th,
+    _tag: PhantomData<fn(U)>,
+}
+
+impl<U, Th> MapTag<U, Th> {
+    pub fn new(theme: Th) -> Self {
+        Self {
+            theme,
+            _tag: PhantomData,
+        }
+    }
+}
+
+impl<T: Tag + Into<U>, U: Tag, Th: Theme<U>> Theme<T> for MapTag<U, Th> {
+    type Result = Th::Result;
+
+    fn translate(from: T) -> Self::Result {
+        Th::translate(from.into())
+    }
+}
+
+impl<T: Tag + Into<U>, U: Tag, Th: RuntimeTheme<U>> RuntimeTheme<T> for MapTag<U, Th> {
+    type Result = Th::Result;
+
+    fn translate(&self, from: T) -> Self::Result {
+        self.theme.translate(from.into())
+    }
+}
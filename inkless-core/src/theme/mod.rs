@@ -1,6 +1,11 @@
 pub mod canvas;
 pub mod ext;
 pub mod clean;
+pub mod chain;
+pub mod fallback;
+pub mod map_tag;
+
+use core::marker::PhantomData;
 
 use crate::tag::Tag;
 
@@ -9,3 +14,64 @@ pub trait Theme<T: Tag> {
 
     fn translate(from: T) -> Self::Result;
 }
+
+/// A theme that translates through `&self`, so it can carry state loaded at runtime, such as a
+/// user's colour preferences.
+///
+/// A `Theme` is not a `RuntimeTheme` by itself. Apply it with `with_theme` where it stands alone,
+/// or wrap it in `StaticTheme` to pass it where a `RuntimeTheme` is expected.
+pub trait RuntimeTheme<T: Tag> {
+    type Result: Tag;
+
+    fn translate(&self, from: T) -> Self::Result;
+}
+
+/// Lets combinators such as `Chain` hold borrowed runtime themes.
+impl<T: Tag, Th: RuntimeTheme<T> + ?Sized> RuntimeTheme<T> for &Th {
+    type Result = Th::Result;
+
+    fn translate(&self, from: T) -> Self::Result {
+        (**self).translate(from)
+    }
+}
+
+/// Adapts a static `Theme` into a `RuntimeTheme`.
+///
+/// This is a wrapper rather than a blanket impl, so other crates can still implement `RuntimeTheme`
+/// for their own types over any tag. It is zero-sized, so `&StaticTheme::<Th>::new()` can be passed
+/// inline.
+pub struct StaticTheme<Th>(PhantomData<fn() -> Th>);
+
+impl<Th> StaticTheme<Th> {
+    pub const fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<Th> Default for StaticTheme<Th> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Th> Clone for StaticTheme<Th> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Th> Copy for StaticTheme<Th> {}
+
+impl<Th> core::fmt::Debug for StaticTheme<Th> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("StaticTheme")
+    }
+}
+
+impl<T: Tag, Th: Theme<T>> RuntimeTheme<T> for StaticTheme<Th> {
+    type Result = Th::Result;
+
+    fn translate(&self, from: T) -> Self::Result {
+        Th::translate(from)
+    }
+}
@@ -409,3 +409,25 @@ pub fn write_hyperlink_delta<W: CharacterWriter, T1: AnsiTag + ?Sized, T2: AnsiT
 
     Ok(())
 }
+
+/// Writes every escape sequence needed to switch from `old`'s style to `new`'s, including the
+/// hyperlink.
+pub fn write_style_delta<W: CharacterWriter, T1: AnsiTag + ?Sized, T2: AnsiTag + ?Sized>(
+    writer: &mut W,
+    support: AnsiSupport,
+    old: Option<&T1>,
+    new: Option<&T2>,
+) -> Result<(), W::Error> {
+    write_intensity_delta(writer, old, new)?;
+    write_blink_delta(writer, old, new)?;
+    write_italic_delta(writer, old, new)?;
+    write_concealed_delta(writer, old, new)?;
+    write_strikethrough_delta(writer, old, new)?;
+    write_underline_style_delta(writer, old, new)?;
+    write_foreground_color_delta(writer, support, old, new)?;
+    write_background_color_delta(writer, support, old, new)?;
+    write_underline_color_delta(writer, support, old, new)?;
+    write_hyperlink_delta(writer, support, old, new)?;
+
+    Ok(())
+}
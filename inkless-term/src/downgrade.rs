@@ -0,0 +1,226 @@
+use core::any::{Any, TypeId};
+
+use inkless_core::{
+    canvas::{Canvas, summary::CanvasSummary, tab_stops::TabStops},
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
+    render_position::RenderPosition,
+    renderable::{Renderable, RenderableError},
+};
+
+use crate::{support::AnsiSupport, tag::AnsiTag};
+
+/// Renders `inner`, reporting `DiagnosticKind::ColorDowngraded` wherever it writes a tag whose
+/// colours `support` cannot show as written.
+///
+/// A run of downgraded graphemes is reported once, at its first grapheme.
+pub struct ReportDowngrades<R> {
+    support: AnsiSupport,
+    inner: R,
+}
+
+impl<R> ReportDowngrades<R> {
+    pub fn new(support: AnsiSupport, inner: R) -> Self {
+        Self { support, inner }
+    }
+}
+
+impl<T: AnsiTag, R: Renderable<T>> Renderable<T> for ReportDowngrades<R> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        DowngradeCanvas::new(canvas, self.support)
+            .write(&self.inner)
+            .map(|_| ())
+    }
+}
+
+/// A canvas that reports `DiagnosticKind::ColorDowngraded` for the graphemes written through it
+/// whose tags `support` cannot show as written, and forwards everything else.
+pub struct DowngradeCanvas<'a, T: AnsiTag> {
+    inner: &'a mut dyn Canvas<T>,
+    support: AnsiSupport,
+
+    /// Whether the last grapheme written was downgraded, so a run is only reported once.
+    downgrading: bool,
+}
+
+impl<'a, T: AnsiTag> DowngradeCanvas<'a, T> {
+    pub fn new(inner: &'a mut dyn Canvas<T>, support: AnsiSupport) -> Self {
+        Self {
+            inner,
+            support,
+            downgrading: false,
+        }
+    }
+
+    /// Reports `tag` before it is written, so the diagnostic points at the grapheme.
+    fn check(&mut self, tag: &T) {
+        let downgrades = self.support.downgrades(tag);
+
+        if downgrades && !self.downgrading {
+            self.inner.report(DiagnosticKind::ColorDowngraded);
+        }
+
+        self.downgrading = downgrades;
+    }
+}
+
+/// Renders a renderable into a `DowngradeCanvas` around the child canvas it is given.
+struct Checked<'r, T: AnsiTag> {
+    renderable: &'r dyn Renderable<T>,
+    support: AnsiSupport,
+}
+
+impl<'r, T: AnsiTag> Renderable<T> for Checked<'r, T> {
+    fn render_into<'buffer_reference>(
+        &self,
+        canvas: &mut dyn Canvas<T>,
+    ) -> Result<(), RenderableError> {
+        self.renderable
+            .render_into(&mut DowngradeCanvas::new(canvas, self.support))
+    }
+}
+
+impl<'a, T: AnsiTag> Canvas<T> for DowngradeCanvas<'a, T> {
+    fn write(&mut self, renderable: &dyn Renderable<T>) -> Result<CanvasSummary, RenderableError> {
+        self.downgrading = false;
+
+        self.inner.write(&Checked {
+            renderable,
+            support: self.support,
+        })
+    }
+
+    fn set_char(&mut self, ch: char, tag: T) -> bool {
+        self.check(&tag);
+        self.inner.set_char(ch, tag)
+    }
+
+    fn set_gph(&mut self, v: &gph, tag: T) -> bool {
+        self.check(&tag);
+        self.inner.set_gph(v, tag)
+    }
+
+    fn can_set_gph(&mut self, v: &gph) -> bool {
+        self.inner.can_set_gph(v)
+    }
+
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<T> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
+    fn get_start_position(&self) -> RenderPosition {
+        self.inner.get_start_position()
+    }
+
+    fn get_position(&self) -> RenderPosition {
+        self.inner.get_position()
+    }
+
+    fn set_position(&mut self, position: RenderPosition) -> &mut dyn Canvas<T> {
+        self.inner.set_position(position);
+        self
+    }
+
+    fn set_column(&mut self, column: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_column(column);
+        self
+    }
+
+    fn set_line(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line(line);
+        self
+    }
+
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<T> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
+    fn cursor_down(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down();
+        self
+    }
+
+    fn cursor_down_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_down_by(count);
+        self
+    }
+
+    fn try_cursor_up(&mut self) -> bool {
+        self.inner.try_cursor_up()
+    }
+
+    fn try_cursor_up_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_up_by(count)
+    }
+
+    fn cursor_up(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up();
+        self
+    }
+
+    fn cursor_up_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_up_by(count);
+        self
+    }
+
+    fn try_cursor_left(&mut self) -> bool {
+        self.inner.try_cursor_left()
+    }
+
+    fn try_cursor_left_by(&mut self, count: usize) -> bool {
+        self.inner.try_cursor_left_by(count)
+    }
+
+    fn cursor_left(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left();
+        self
+    }
+
+    fn cursor_left_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_left_by(count);
+        self
+    }
+
+    fn cursor_right(&mut self) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right();
+        self
+    }
+
+    fn cursor_right_by(&mut self, count: usize) -> &mut dyn Canvas<T> {
+        self.inner.cursor_right_by(count);
+        self
+    }
+}
@@ -0,0 +1,291 @@
+use alloc::vec::Vec;
+use core::{mem, ops::ControlFlow};
+
+use inkless_core::{
+    canvas::AmbiguityPolicy,
+    grapheme::{gph, grapheme::Grapheme, width::WidthProvider},
+    tag::{Tag, sink::TagSink},
+    writer::character::CharacterWriter,
+};
+
+use crate::{
+    delta::write_style_delta,
+    sink::PlaintextError,
+    support::AnsiSupport,
+    tag::{default::Ansi, indirection::AnsiDeref},
+    utils::{write_cursor_column, write_cursor_down, write_cursor_up},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum FrameCell {
+    Gap,
+    Grapheme(Ansi, Grapheme),
+    /// Covered by the right half of the wide grapheme to its left.
+    Continuation,
+}
+
+/// The cells of the last frame drawn to a terminal, used to redraw only what changed.
+///
+/// A frame is drawn starting at column 0 of the line the cursor is on when the first frame is
+/// rendered. Every frame after that is diffed against the previous one, and only the changed runs
+/// of cells are written, using relative cursor movement to reach them. Lines the previous frame had
+/// but the new one does not are cleared.
+///
+/// After each frame the cursor is parked at the start of the line below it, and the terminal's
+/// style is reset. Anything else that moves the cursor or draws over the frame in between must be
+/// followed by `invalidate`.
+///
+/// Hyperlinks are not tracked, since the cells are kept as `Ansi` tags.
+#[derive(Debug)]
+pub struct AnsiFrame<P: WidthProvider = AmbiguityPolicy> {
+    rows: Vec<Vec<FrameCell>>,
+    support: AnsiSupport,
+    width_provider: P,
+    cursor_line: usize,
+    cursor_column: usize,
+    lines_drawn: usize,
+}
+
+impl<P: WidthProvider> AnsiFrame<P> {
+    /// Creates an empty frame. `width_provider` must agree with the one the buffer renders with.
+    pub fn new(support: AnsiSupport, width_provider: P) -> Self {
+        Self {
+            rows: Vec::new(),
+            support,
+            width_provider,
+            cursor_line: 0,
+            cursor_column: 0,
+            lines_drawn: 1,
+        }
+    }
+
+    /// Returns a sink that draws the next frame to `writer`.
+    pub fn sink<W: CharacterWriter>(&mut self, writer: W) -> AnsiFrameSink<'_, W, P> {
+        AnsiFrameSink {
+            frame: self,
+            writer,
+            result: Ok(()),
+            line: 0,
+            row: Vec::new(),
+            last_tag: None,
+        }
+    }
+
+    /// Forgets the previous frame's cells, so the next frame is drawn in full.
+    pub fn invalidate(&mut self) {
+        self.rows.clear();
+    }
+}
+
+/// A `TagSink` that draws a single frame of an `AnsiFrame`.
+pub struct AnsiFrameSink<'f, W: CharacterWriter, P: WidthProvider = AmbiguityPolicy> {
+    frame: &'f mut AnsiFrame<P>,
+    writer: W,
+    result: Result<(), W::Error>,
+    line: usize,
+    row: Vec<FrameCell>,
+    last_tag: Option<Ansi>,
+}
+
+impl<'f, W: CharacterWriter, P: WidthProvider> AnsiFrameSink<'f, W, P> {
+    fn move_to(&mut self, line: usize, column: usize) -> Result<(), W::Error> {
+        let frame = &mut *self.frame;
+
+        if line < frame.cursor_line {
+            write_cursor_up(&mut self.writer, frame.cursor_line - line)?;
+            frame.cursor_line = line;
+        } else if line > frame.cursor_line {
+            // Cursor movement cannot go past the last line on screen, so new lines are made with
+            // line breaks instead.
+            let last_drawn = line.min(frame.lines_drawn - 1);
+
+            if last_drawn > frame.cursor_line {
+                write_cursor_down(&mut self.writer, last_drawn - frame.cursor_line)?;
+                frame.cursor_line = last_drawn;
+            }
+
+            while frame.cursor_line < line {
+                self.writer.write_str("\r\n")?;
+                frame.cursor_line += 1;
+                frame.cursor_column = 0;
+            }
+
+            frame.lines_drawn = frame.lines_drawn.max(line + 1);
+        }
+
+        if frame.cursor_column != column {
+            write_cursor_column(&mut self.writer, column)?;
+            frame.cursor_column = column;
+        }
+
+        Ok(())
+    }
+
+    fn set_style(&mut self, tag: Option<&Ansi>) -> Result<(), W::Error> {
+        write_style_delta(
+            &mut self.writer,
+            self.frame.support,
+            self.last_tag.as_ref(),
+            tag,
+        )?;
+
+        self.last_tag = tag.copied();
+
+        Ok(())
+    }
+
+    fn flush_line(&mut self) -> Result<(), W::Error> {
+        let row = mem::take(&mut self.row);
+        let old = self
+            .frame
+            .rows
+            .get_mut(self.line)
+            .map(mem::take)
+            .unwrap_or_default();
+
+        for (column, cell) in row.iter().enumerate() {
+            if old.get(column) == Some(cell) {
+                continue;
+            }
+
+            match cell {
+                FrameCell::Continuation => {}
+                FrameCell::Gap => {
+                    self.move_to(self.line, column)?;
+                    self.set_style(None)?;
+                    self.writer.write_str(" ")?;
+                    self.frame.cursor_column += 1;
+                }
+                FrameCell::Grapheme(tag, grapheme) => {
+                    self.move_to(self.line, column)?;
+                    self.set_style(Some(tag))?;
+                    self.writer.write_str(grapheme.as_str())?;
+                    self.frame.cursor_column += grapheme.width(&self.frame.width_provider);
+                }
+            }
+        }
+
+        if old.len() > row.len() {
+            self.move_to(self.line, row.len())?;
+            self.set_style(None)?;
+            self.writer.write_str("\x1b[K")?;
+        }
+
+        if self.line < self.frame.rows.len() {
+            self.frame.rows[self.line] = row;
+        } else {
+            self.frame.rows.push(row);
+        }
+
+        self.line += 1;
+
+        Ok(())
+    }
+
+    fn finish(&mut self) -> Result<(), W::Error> {
+        for line in self.line..self.frame.rows.len() {
+            if !self.frame.rows[line].is_empty() {
+                self.move_to(line, 0)?;
+                self.set_style(None)?;
+                self.writer.write_str("\x1b[2K")?;
+            }
+        }
+
+        self.frame.rows.truncate(self.line);
+
+        self.set_style(None)?;
+        self.move_to(self.line, 0)?;
+        self.writer.write_str("\x1b[0m")
+    }
+}
+
+impl<'f, W: CharacterWriter, P: WidthProvider, T: Tag + AnsiDeref> TagSink<T>
+    for AnsiFrameSink<'f, W, P>
+{
+    type Result = Result<W, PlaintextError<W::Error>>;
+
+    fn append(&mut self, grapheme: &gph, tag: T) -> ControlFlow<()> {
+        let width = grapheme.width(&self.frame.width_provider);
+
+        self.row.push(FrameCell::Grapheme(
+            Ansi::from_tag(tag),
+            Grapheme::from(grapheme),
+        ));
+
+        for _ in 1..width {
+            self.row.push(FrameCell::Continuation);
+        }
+
+        ControlFlow::Continue(())
+    }
+
+    fn gap(&mut self) -> ControlFlow<()> {
+        self.row.push(FrameCell::Gap);
+
+        ControlFlow::Continue(())
+    }
+
+    fn finalize_line(&mut self) -> ControlFlow<()> {
+        self.result = self.flush_line();
+
+        if self.result.is_err() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn finalize(mut self) -> Self::Result {
+        if self.result.is_ok() {
+            self.result = self.finish();
+        }
+
+        if let Err(e) = self.result {
+            // What reached the terminal is unknown, so the next frame has to be drawn in full.
+            self.frame.invalidate();
+
+            return Err(PlaintextError::Writer(e));
+        }
+
+        Ok(self.writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::String;
+
+    use inkless_core::{canvas::AmbiguityPolicy, grapheme::gph, tag::sink::TagSink};
+
+    use super::AnsiFrame;
+    use crate::{support::AnsiSupport, tag::default::Ansi};
+
+    fn draw(frame: &mut AnsiFrame, lines: &[&str]) -> String {
+        let mut sink = frame.sink(String::new());
+
+        for line in lines {
+            for grapheme in gph::from_str(line) {
+                let _ = sink.append(grapheme, Ansi::new());
+            }
+
+            let _ = TagSink::<Ansi>::finalize_line(&mut sink);
+        }
+
+        TagSink::<Ansi>::finalize(sink).unwrap()
+    }
+
+    #[test]
+    fn repaint_writes_only_changed_cells() {
+        let mut frame = AnsiFrame::new(AnsiSupport::none(), AmbiguityPolicy::Standard);
+
+        assert_eq!(draw(&mut frame, &["abc", "def"]), "abc\r\ndef\r\n\x1b[0m");
+
+        // Up two lines to the changed cell, then back below the frame.
+        assert_eq!(
+            draw(&mut frame, &["abX", "def"]),
+            "\x1b[2A\x1b[3GX\x1b[2B\x1b[1G\x1b[0m"
+        );
+
+        assert_eq!(draw(&mut frame, &["abX", "def"]), "\x1b[0m");
+    }
+}
@@ -0,0 +1,465 @@
+use core::ops::ControlFlow;
+
+use inkless_core::{
+    builtins::sinks::line_format::{LineFormat, LineFormatter},
+    grapheme::gph,
+    tag::{Tag, sink::TagSink},
+    writer::character::CharacterWriter,
+};
+
+use crate::{
+    palette::Palette,
+    sink::PlaintextError,
+    styles::{BlinkSpeed, Intensity, TrueColor, UnderlineStyle},
+    tag::{AnsiTag, default::Ansi},
+};
+
+/// A tag that can be styled with CSS.
+pub trait CssTag: Tag {
+    /// Writes the inline CSS declarations for this tag, each terminated with `;`.
+    ///
+    /// Writes nothing for a tag with no style.
+    fn write_css<W: CharacterWriter + ?Sized>(
+        &self,
+        writer: &mut W,
+        palette: &Palette,
+    ) -> Result<(), W::Error>;
+
+    /// A class to put on this tag's `<span>`, for styles that need a stylesheet.
+    fn css_class(&self) -> Option<&str> {
+        None
+    }
+
+    /// A URL to wrap this tag's text in a link to.
+    ///
+    /// `HtmlSink` only links `http`, `https`, `mailto` and relative URLs, and writes the text
+    /// unlinked for any other scheme.
+    fn href(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Writes the CSS for any `AnsiTag`, for use in `CssTag` impls.
+///
+/// Colours are resolved through `palette`, intensity maps to `font-weight` or `opacity`, and
+/// underline styles map to `text-decoration`. Blinking has no CSS equivalent, so `Ansi` sets the
+/// `inkless-blink` or `inkless-blink-rapid` class instead.
+pub fn write_ansi_css<W: CharacterWriter + ?Sized, T: AnsiTag + ?Sized>(
+    writer: &mut W,
+    tag: &T,
+    palette: &Palette,
+) -> Result<(), W::Error> {
+    if let Some(color) = palette.foreground(tag) {
+        writer.write_str("color:")?;
+        write_hex(writer, color)?;
+        writer.write_str(";")?;
+    }
+
+    if let Some(color) = palette.background(tag) {
+        writer.write_str("background-color:")?;
+        write_hex(writer, color)?;
+        writer.write_str(";")?;
+    }
+
+    match tag.get_intensity() {
+        Intensity::Bold => writer.write_str("font-weight:bold;")?,
+        Intensity::Faint => writer.write_str("opacity:0.5;")?,
+        Intensity::Normal => {}
+    }
+
+    if tag.is_italic() {
+        writer.write_str("font-style:italic;")?;
+    }
+
+    let underline = tag.get_underline();
+
+    match (underline.is_some(), tag.is_strikethrough()) {
+        (true, true) => writer.write_str("text-decoration-line:underline line-through;")?,
+        (true, false) => writer.write_str("text-decoration-line:underline;")?,
+        (false, true) => writer.write_str("text-decoration-line:line-through;")?,
+        (false, false) => {}
+    }
+
+    match underline {
+        Some(UnderlineStyle::Double) => writer.write_str("text-decoration-style:double;")?,
+        Some(UnderlineStyle::Curly) => writer.write_str("text-decoration-style:wavy;")?,
+        Some(UnderlineStyle::Dotted) => writer.write_str("text-decoration-style:dotted;")?,
+        Some(UnderlineStyle::Dashed) => writer.write_str("text-decoration-style:dashed;")?,
+        Some(UnderlineStyle::Single) | None => {}
+    }
+
+    if let Some(color) = underline.and_then(|_| palette.underline(tag)) {
+        writer.write_str("text-decoration-color:")?;
+        write_hex(writer, color)?;
+        writer.write_str(";")?;
+    }
+
+    if tag.is_concealed() {
+        writer.write_str("visibility:hidden;")?;
+    }
+
+    Ok(())
+}
+
+impl CssTag for Ansi {
+    fn write_css<W: CharacterWriter + ?Sized>(
+        &self,
+        writer: &mut W,
+        palette: &Palette,
+    ) -> Result<(), W::Error> {
+        write_ansi_css(writer, self, palette)
+    }
+
+    fn css_class(&self) -> Option<&str> {
+        match self.get_blink_speed() {
+            Some(BlinkSpeed::Slow) => Some("inkless-blink"),
+            Some(BlinkSpeed::Rapid) => Some("inkless-blink-rapid"),
+            None => None,
+        }
+    }
+
+    fn href(&self) -> Option<&str> {
+        self.hyperlink_url()
+    }
+}
+
+/// A sink that writes a `<pre class="inkless">` block, with runs of equal tags grouped into
+/// `<span>`s styled through `CssTag`.
+///
+/// The block is drawn in the palette's foreground and background colours, and is preceded by a
+/// `<style>` element defining the `inkless-blink` and `inkless-blink-rapid` classes unless
+/// `with_stylesheet` turns it off.
+///
+/// Gaps and line endings are written the way `AnsiSink` writes them, so the block matches what a
+/// terminal would show.
+pub struct HtmlSink<W: CharacterWriter, T> {
+    writer: W,
+    result: Result<(), W::Error>,
+    palette: Palette,
+    format: LineFormatter,
+    stylesheet: bool,
+
+    started: bool,
+    last_tag: Option<T>,
+    open_span: bool,
+    open_link: bool,
+}
+
+impl<W: CharacterWriter, T> From<W> for HtmlSink<W, T> {
+    fn from(value: W) -> Self {
+        HtmlSink {
+            writer: value,
+            result: Ok(()),
+            palette: Palette::default(),
+            format: LineFormatter::default(),
+            stylesheet: true,
+            started: false,
+            last_tag: None,
+            open_span: false,
+            open_link: false,
+        }
+    }
+}
+
+impl<W: CharacterWriter, T: CssTag + PartialEq> HtmlSink<W, T> {
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn with_line_format(mut self, format: LineFormat) -> Self {
+        self.format = LineFormatter::new(format);
+        self
+    }
+
+    /// Whether to write the `<style>` element, which can be left out when the page already
+    /// defines the blink classes.
+    pub fn with_stylesheet(mut self, stylesheet: bool) -> Self {
+        self.stylesheet = stylesheet;
+        self
+    }
+
+    fn start(&mut self) -> Result<(), W::Error> {
+        if self.started {
+            return Ok(());
+        }
+
+        self.started = true;
+
+        if self.stylesheet {
+            self.writer.write_str(STYLESHEET)?;
+        }
+
+        self.writer
+            .write_str("<pre class=\"inkless\" style=\"color:")?;
+        write_hex(&mut self.writer, self.palette.foreground)?;
+        self.writer.write_str(";background-color:")?;
+        write_hex(&mut self.writer, self.palette.background)?;
+        self.writer.write_str(";\">")
+    }
+
+    fn close(&mut self) -> Result<(), W::Error> {
+        if self.open_span {
+            self.open_span = false;
+            self.writer.write_str("</span>")?;
+        }
+
+        if self.open_link {
+            self.open_link = false;
+            self.writer.write_str("</a>")?;
+        }
+
+        Ok(())
+    }
+
+    fn open(&mut self, tag: &T) -> Result<(), W::Error> {
+        if let Some(href) = tag.href().filter(|href| is_safe_href(href)) {
+            self.open_link = true;
+            self.writer.write_str("<a href=\"")?;
+            write_escaped(&mut self.writer, href)?;
+            self.writer.write_str("\">")?;
+        }
+
+        let mut probe = Probe(false);
+        let _ = tag.write_css(&mut probe, &self.palette);
+        let class = tag.css_class();
+
+        if !probe.0 && class.is_none() {
+            return Ok(());
+        }
+
+        self.open_span = true;
+        self.writer.write_str("<span")?;
+
+        if let Some(class) = class {
+            self.writer.write_str(" class=\"")?;
+            write_escaped(&mut self.writer, class)?;
+            self.writer.write_str("\"")?;
+        }
+
+        if probe.0 {
+            self.writer.write_str(" style=\"")?;
+            tag.write_css(&mut Escaping(&mut self.writer), &self.palette)?;
+            self.writer.write_str("\"")?;
+        }
+
+        self.writer.write_str(">")
+    }
+
+    fn append_internal(&mut self, grapheme: &gph, tag: T) -> Result<(), W::Error> {
+        self.start()?;
+        self.format.content(&mut self.writer)?;
+
+        if self.last_tag.as_ref() != Some(&tag) {
+            self.close()?;
+            self.open(&tag)?;
+            self.last_tag = Some(tag);
+        }
+
+        write_escaped(&mut self.writer, grapheme.as_str())
+    }
+}
+
+impl<W: CharacterWriter, T: CssTag + PartialEq> TagSink<T> for HtmlSink<W, T> {
+    type Result = Result<W, PlaintextError<W::Error>>;
+
+    fn append(&mut self, grapheme: &gph, tag: T) -> ControlFlow<()> {
+        self.result = self.append_internal(grapheme, tag);
+
+        if self.result.is_err() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn gap(&mut self) -> ControlFlow<()> {
+        self.result = self.start().and_then(|_| self.format.gap(&mut self.writer));
+
+        if self.result.is_err() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn finalize_line(&mut self) -> ControlFlow<()> {
+        self.result = self
+            .start()
+            .and_then(|_| self.format.end_line(&mut self.writer));
+
+        if self.result.is_err() {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    }
+
+    fn finalize(mut self) -> Self::Result {
+        if let Err(e) = self.result {
+            return Err(PlaintextError::Writer(e));
+        }
+
+        self.start().map_err(PlaintextError::Writer)?;
+        self.format
+            .finish(&mut self.writer)
+            .map_err(PlaintextError::Writer)?;
+        self.close().map_err(PlaintextError::Writer)?;
+        self.writer
+            .write_str("</pre>")
+            .map_err(PlaintextError::Writer)?;
+
+        Ok(self.writer)
+    }
+}
+
+/// Blinks the text of the classes `Ansi` uses for blinking, at roughly the rates terminals do.
+const STYLESHEET: &str = "<style>\
+@keyframes inkless-blink{50%{visibility:hidden}}\
+.inkless-blink{animation:inkless-blink 1s step-end infinite}\
+.inkless-blink-rapid{animation:inkless-blink 0.4s step-end infinite}\
+</style>";
+
+/// Whether `href` can be put in a link without running anything when clicked.
+///
+/// Only `http`, `https` and `mailto` URLs and relative URLs are allowed, so `javascript:` and
+/// `data:` links are written as plain text.
+fn is_safe_href(href: &str) -> bool {
+    // A colon before any `/`, `?` or `#` ends a scheme; without one the URL is relative.
+    let Some(end) = href.find([':', '/', '?', '#']) else {
+        return true;
+    };
+
+    if href.as_bytes()[end] != b':' {
+        return true;
+    }
+
+    let scheme = &href[..end];
+
+    ["http", "https", "mailto"]
+        .iter()
+        .any(|allowed| scheme.eq_ignore_ascii_case(allowed))
+}
+
+/// Records whether anything was written, without keeping it.
+struct Probe(bool);
+
+impl CharacterWriter for Probe {
+    type Error = core::convert::Infallible;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        self.0 |= !s.is_empty();
+        Ok(())
+    }
+}
+
+/// Escapes everything written through it for use in HTML text or a quoted attribute.
+struct Escaping<'w, W: CharacterWriter>(&'w mut W);
+
+impl<'w, W: CharacterWriter> CharacterWriter for Escaping<'w, W> {
+    type Error = W::Error;
+
+    fn write_str(&mut self, s: &str) -> Result<(), Self::Error> {
+        write_escaped(self.0, s)
+    }
+}
+
+pub(crate) fn write_escaped<W: CharacterWriter + ?Sized>(
+    writer: &mut W,
+    s: &str,
+) -> Result<(), W::Error> {
+    let mut rest = s;
+
+    while let Some(index) = rest.find(['&', '<', '>', '"', '\'']) {
+        writer.write_str(&rest[..index])?;
+        writer.write_str(match rest.as_bytes()[index] {
+            b'&' => "&amp;",
+            b'<' => "&lt;",
+            b'>' => "&gt;",
+            b'"' => "&quot;",
+            _ => "&#39;",
+        })?;
+        rest = &rest[index + 1..];
+    }
+
+    writer.write_str(rest)
+}
+
+pub(crate) fn write_hex<W: CharacterWriter + ?Sized>(
+    writer: &mut W,
+    color: TrueColor,
+) -> Result<(), W::Error> {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut buf = [b'#'; 7];
+
+    for (i, channel) in [color.0, color.1, color.2].into_iter().enumerate() {
+        buf[1 + i * 2] = DIGITS[(channel >> 4) as usize];
+        buf[2 + i * 2] = DIGITS[(channel & 0xf) as usize];
+    }
+
+    // `buf` contains only ASCII.
+    writer.write_str(core::str::from_utf8(&buf).unwrap())
+}
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::String;
+
+    use inkless_core::{
+        grapheme::gph,
+        tag::{Tag, sink::TagSink},
+        writer::character::CharacterWriter,
+    };
+
+    use super::{CssTag, HtmlSink};
+    use crate::palette::Palette;
+
+    #[derive(PartialEq)]
+    struct Link(&'static str);
+
+    impl Tag for Link {}
+
+    impl CssTag for Link {
+        fn write_css<W: CharacterWriter + ?Sized>(
+            &self,
+            _writer: &mut W,
+            _palette: &Palette,
+        ) -> Result<(), W::Error> {
+            Ok(())
+        }
+
+        fn href(&self) -> Option<&str> {
+            Some(self.0)
+        }
+    }
+
+    fn link(href: &'static str) -> String {
+        let mut sink = HtmlSink::from(String::new()).with_stylesheet(false);
+
+        for grapheme in gph::from_str("go") {
+            let _ = sink.append(grapheme, Link(href));
+        }
+
+        let html = sink.finalize().unwrap();
+        let start = html.find('>').unwrap() + 1;
+
+        html[start..html.len() - "</pre>".len()].into()
+    }
+
+    #[test]
+    fn links_only_allowed_schemes() {
+        assert_eq!(
+            link("https://example.com"),
+            "<a href=\"https://example.com\">go</a>"
+        );
+        assert_eq!(
+            link("MAILTO:me@example.com"),
+            "<a href=\"MAILTO:me@example.com\">go</a>"
+        );
+        assert_eq!(link("/docs?page=1"), "<a href=\"/docs?page=1\">go</a>");
+        assert_eq!(link("javascript:alert(1)"), "go");
+        assert_eq!(link("data:text/html,hi"), "go");
+        assert_eq!(link(" javascript:alert(1)"), "go");
+    }
+}
@@ -1,10 +1,19 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod delta;
+pub mod downgrade;
+#[cfg(feature = "alloc")]
+pub mod frame;
+pub mod html;
+pub mod palette;
 pub mod sink;
 pub mod styles;
 pub mod support;
+#[cfg(feature = "alloc")]
+pub mod svg;
 pub mod tag;
+#[cfg(feature = "alloc")]
+pub mod theme;
 pub mod utils;
 
 #[cfg(feature = "std")]
@@ -0,0 +1,110 @@
+use crate::{
+    styles::{Ansi8Color, Ansi16Color, Ansi256Color, TrueColor},
+    tag::AnsiTag,
+    utils::ansi8_index,
+};
+
+/// The RGB values a terminal draws the indexed colours with, for sinks that render outside one.
+///
+/// Lookups pick the most specific colour a tag sets, the way a truecolor terminal would.
+///
+/// More colours may be added, so start from a palette such as `XTERM` and overwrite its fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Palette {
+    /// Colours 0–7.
+    pub normal: [TrueColor; 8],
+
+    /// Colours 8–15.
+    pub bright: [TrueColor; 8],
+
+    /// The colour of text with no foreground colour set.
+    pub foreground: TrueColor,
+
+    /// The colour behind cells with no background colour set.
+    pub background: TrueColor,
+}
+
+impl Palette {
+    /// The colours xterm uses by default, on a dark background.
+    pub const XTERM: Self = Self {
+        normal: [
+            TrueColor(0x00, 0x00, 0x00),
+            TrueColor(0xcd, 0x00, 0x00),
+            TrueColor(0x00, 0xcd, 0x00),
+            TrueColor(0xcd, 0xcd, 0x00),
+            TrueColor(0x00, 0x00, 0xee),
+            TrueColor(0xcd, 0x00, 0xcd),
+            TrueColor(0x00, 0xcd, 0xcd),
+            TrueColor(0xe5, 0xe5, 0xe5),
+        ],
+        bright: [
+            TrueColor(0x7f, 0x7f, 0x7f),
+            TrueColor(0xff, 0x00, 0x00),
+            TrueColor(0x00, 0xff, 0x00),
+            TrueColor(0xff, 0xff, 0x00),
+            TrueColor(0x5c, 0x5c, 0xff),
+            TrueColor(0xff, 0x00, 0xff),
+            TrueColor(0x00, 0xff, 0xff),
+            TrueColor(0xff, 0xff, 0xff),
+        ],
+        foreground: TrueColor(0xe5, 0xe5, 0xe5),
+        background: TrueColor(0x00, 0x00, 0x00),
+    };
+
+    pub fn ansi8(&self, color: Ansi8Color) -> TrueColor {
+        self.normal[ansi8_index(color) as usize]
+    }
+
+    pub fn ansi16(&self, color: Ansi16Color) -> TrueColor {
+        match color {
+            Ansi16Color::Normal(color) => self.ansi8(color),
+            Ansi16Color::Bright(color) => self.bright[ansi8_index(color) as usize],
+        }
+    }
+
+    /// The 6×6×6 cube and grayscale ramp are fixed; only the first 16 colours come from the palette.
+    pub fn ansi256(&self, color: Ansi256Color) -> TrueColor {
+        fn level(value: u8) -> u8 {
+            match value.min(5) {
+                0 => 0,
+                value => 55 + value * 40,
+            }
+        }
+
+        match color {
+            Ansi256Color::Normal(color) => self.ansi16(Ansi16Color::Normal(color)),
+            Ansi256Color::Bright(color) => self.ansi16(Ansi16Color::Bright(color)),
+            Ansi256Color::Rgb(r, g, b) => TrueColor(level(r), level(g), level(b)),
+            Ansi256Color::Grayscale(value) => {
+                let value = 8 + value.min(23) * 10;
+                TrueColor(value, value, value)
+            }
+        }
+    }
+
+    pub fn foreground<T: AnsiTag + ?Sized>(&self, tag: &T) -> Option<TrueColor> {
+        tag.get_true_foreground_color()
+            .or_else(|| tag.get_ansi256_foreground_color().map(|c| self.ansi256(c)))
+            .or_else(|| tag.get_ansi16_foreground_color().map(|c| self.ansi16(c)))
+            .or_else(|| tag.get_ansi8_foreground_color().map(|c| self.ansi8(c)))
+    }
+
+    pub fn background<T: AnsiTag + ?Sized>(&self, tag: &T) -> Option<TrueColor> {
+        tag.get_true_background_color()
+            .or_else(|| tag.get_ansi256_background_color().map(|c| self.ansi256(c)))
+            .or_else(|| tag.get_ansi16_background_color().map(|c| self.ansi16(c)))
+            .or_else(|| tag.get_ansi8_background_color().map(|c| self.ansi8(c)))
+    }
+
+    pub fn underline<T: AnsiTag + ?Sized>(&self, tag: &T) -> Option<TrueColor> {
+        tag.get_true_underline_color()
+            .or_else(|| tag.get_ansi256_underline_color().map(|c| self.ansi256(c)))
+    }
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self::XTERM
+    }
+}
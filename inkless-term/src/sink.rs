@@ -1,6 +1,7 @@
 use core::ops::ControlFlow;
 
 use inkless_core::{
+    builtins::sinks::line_format::{LineFormat, LineFormatter},
     grapheme::gph,
     renderable::RenderableError,
     tag::{Tag, sink::TagSink},
@@ -9,12 +10,7 @@ use inkless_core::{
 use thiserror::Error;
 
 use crate::{
-    delta::{
-        write_background_color_delta, write_blink_delta, write_concealed_delta,
-        write_foreground_color_delta, write_hyperlink_delta, write_intensity_delta,
-        write_italic_delta, write_strikethrough_delta, write_underline_color_delta,
-        write_underline_style_delta,
-    },
+    delta::{write_hyperlink_delta, write_style_delta},
     support::AnsiSupport,
     tag::{default::Ansi, indirection::AnsiDeref},
 };
@@ -24,72 +20,23 @@ pub struct AnsiSink<W: CharacterWriter> {
     pub(crate) result: Result<(), W::Error>,
     pub(crate) support: AnsiSupport,
     pub(crate) last_tag: Option<Ansi>,
+    pub(crate) format: LineFormatter,
 }
 
 impl<W: CharacterWriter> AnsiSink<W> {
+    pub fn with_line_format(mut self, format: LineFormat) -> Self {
+        self.format = LineFormatter::new(format);
+        self
+    }
+
     fn append_internal<T2: AnsiDeref>(
         &mut self,
         grapheme: &gph,
         tag: Option<T2>,
     ) -> Result<(), W::Error> {
-        write_intensity_delta(
-            &mut self.writer,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_blink_delta(
-            &mut self.writer,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_italic_delta(
-            &mut self.writer,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_concealed_delta(
-            &mut self.writer,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_strikethrough_delta(
-            &mut self.writer,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
+        self.format.content(&mut self.writer)?;
 
-        write_underline_style_delta(
-            &mut self.writer,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_foreground_color_delta(
-            &mut self.writer,
-            self.support,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_background_color_delta(
-            &mut self.writer,
-            self.support,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_underline_color_delta(
-            &mut self.writer,
-            self.support,
-            self.last_tag.as_ref(),
-            tag.as_ref().map(|v| v.deref()),
-        )?;
-
-        write_hyperlink_delta(
+        write_style_delta(
             &mut self.writer,
             self.support,
             self.last_tag.as_ref(),
@@ -124,7 +71,7 @@ impl<W: CharacterWriter, T: Tag + AnsiDeref> TagSink<T> for AnsiSink<W> {
     }
 
     fn gap(&mut self) -> ControlFlow<()> {
-        self.result = self.writer.write_str(" ");
+        self.result = self.format.gap(&mut self.writer);
 
         if self.result.is_err() {
             ControlFlow::Break(())
@@ -134,7 +81,7 @@ impl<W: CharacterWriter, T: Tag + AnsiDeref> TagSink<T> for AnsiSink<W> {
     }
 
     fn finalize_line(&mut self) -> ControlFlow<()> {
-        self.result = self.writer.write_str("\n");
+        self.result = self.format.end_line(&mut self.writer);
 
         if self.result.is_err() {
             ControlFlow::Break(())
@@ -148,6 +95,10 @@ impl<W: CharacterWriter, T: Tag + AnsiDeref> TagSink<T> for AnsiSink<W> {
             return Err(PlaintextError::Writer(e));
         }
 
+        self.format
+            .finish(&mut self.writer)
+            .map_err(PlaintextError::Writer)?;
+
         write_hyperlink_delta::<_, _, Ansi>(
             &mut self.writer,
             self.support,
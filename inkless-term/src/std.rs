@@ -1,6 +1,8 @@
 use std::{io::StdoutLock, sync::LazyLock};
 
-use inkless_core::writer::character::CharacterWriter;
+use inkless_core::{
+    builtins::sinks::line_format::LineFormatter, writer::character::CharacterWriter,
+};
 
 use crate::{
     sink::AnsiSink,
@@ -18,7 +20,7 @@ impl AnsiEnv<'static> {
         #[cfg(unix)]
         let is_tty = {
             use std::os::unix::io::AsRawFd;
-            unsafe { libc::isatty(io::stdout().as_raw_fd()) != 0 };
+            unsafe { libc::isatty(io::stdout().as_raw_fd()) != 0 }
         };
 
         #[cfg(windows)]
@@ -76,6 +78,7 @@ impl<'a> AnsiSink<IoWriter<StdoutLock<'a>>> {
             support: AnsiSupport::from_env(),
             writer: IoWriter(std::io::stdout().lock()),
             last_tag: None,
+            format: LineFormatter::default(),
         }
     }
 }
@@ -1,4 +1,8 @@
-use crate::utils::StrIgnoreCaseExt;
+use crate::{
+    styles::{Ansi16Color, Ansi256Color, TrueColor},
+    tag::AnsiTag,
+    utils::StrIgnoreCaseExt,
+};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AnsiColorSupport {
@@ -77,6 +81,91 @@ impl AnsiSupport {
             hyperlinks,
         }
     }
+
+    /// Whether `tag` sets a colour that cannot be shown as written with this support, so a less
+    /// precise one, or none at all, would be shown instead.
+    ///
+    /// Tags usually set a colour in several representations as fallbacks, e.g. `Ansi::red` sets
+    /// both the 8 and 16 colour red, so only a fallback that shows a different colour counts.
+    pub fn downgrades<T: AnsiTag + ?Sized>(&self, tag: &T) -> bool {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Shown {
+            Indexed(Ansi256Color),
+            True(TrueColor),
+        }
+
+        fn indexed(color: Ansi16Color) -> Shown {
+            Shown::Indexed(match color {
+                Ansi16Color::Normal(color) => Ansi256Color::Normal(color),
+                Ansi16Color::Bright(color) => Ansi256Color::Bright(color),
+            })
+        }
+
+        // The colour shown with support up to `level`, from 1 (8 colours) to 4 (RGB).
+        let foreground = |level: u8| {
+            tag.get_true_foreground_color()
+                .filter(|_| level >= 4)
+                .map(Shown::True)
+                .or(tag
+                    .get_ansi256_foreground_color()
+                    .filter(|_| level >= 3)
+                    .map(Shown::Indexed))
+                .or(tag
+                    .get_ansi16_foreground_color()
+                    .filter(|_| level >= 2)
+                    .map(indexed))
+                .or(tag
+                    .get_ansi8_foreground_color()
+                    .filter(|_| level >= 1)
+                    .map(|color| indexed(Ansi16Color::Normal(color))))
+        };
+
+        let background = |level: u8| {
+            tag.get_true_background_color()
+                .filter(|_| level >= 4)
+                .map(Shown::True)
+                .or(tag
+                    .get_ansi256_background_color()
+                    .filter(|_| level >= 3)
+                    .map(Shown::Indexed))
+                .or(tag
+                    .get_ansi16_background_color()
+                    .filter(|_| level >= 2)
+                    .map(indexed))
+                .or(tag
+                    .get_ansi8_background_color()
+                    .filter(|_| level >= 1)
+                    .map(|color| indexed(Ansi16Color::Normal(color))))
+        };
+
+        let underline = |level: u8| {
+            tag.get_true_underline_color()
+                .filter(|_| level >= 4)
+                .map(Shown::True)
+                .or(tag
+                    .get_ansi256_underline_color()
+                    .filter(|_| level >= 3)
+                    .map(Shown::Indexed))
+        };
+
+        let color = match self.color {
+            AnsiColorSupport::None => 0,
+            AnsiColorSupport::Ansi8 => 1,
+            AnsiColorSupport::Ansi16 => 2,
+            AnsiColorSupport::Ansi256 => 3,
+            AnsiColorSupport::TrueColor => 4,
+        };
+
+        let underline_color = match self.underline {
+            AnsiUnderlineColorSupport::None => 0,
+            AnsiUnderlineColorSupport::Ansi256 => 3,
+            AnsiUnderlineColorSupport::TrueColor => 4,
+        };
+
+        foreground(color) != foreground(4)
+            || background(color) != background(4)
+            || underline(underline_color) != underline(4)
+    }
 }
 
 impl<'a> From<AnsiEnv<'a>> for AnsiSupport {
@@ -0,0 +1,377 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Write, ops::ControlFlow};
+
+use inkless_core::{
+    canvas::AmbiguityPolicy,
+    grapheme::{gph, grapheme::Grapheme, width::WidthProvider},
+    tag::{Tag, sink::TagSink},
+    writer::character::CharacterWriter,
+};
+
+use crate::{
+    html::{write_escaped, write_hex},
+    palette::Palette,
+    sink::PlaintextError,
+    styles::{Intensity, UnderlineStyle},
+    tag::{AnsiTag, default::Ansi, indirection::AnsiDeref},
+};
+
+/// The size of the grid an `SvgSink` lays cells out on, in pixels.
+///
+/// Every grapheme is positioned on its own cell, so the image does not depend on the font's advance
+/// widths, only on its glyphs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SvgMetrics {
+    pub cell_width: usize,
+    pub line_height: usize,
+    pub font_size: usize,
+
+    /// The space between the grid and the edge of the window.
+    pub padding: usize,
+}
+
+impl Default for SvgMetrics {
+    fn default() -> Self {
+        Self {
+            cell_width: 9,
+            line_height: 20,
+            font_size: 15,
+            padding: 16,
+        }
+    }
+}
+
+const CHROME_HEIGHT: usize = 32;
+
+#[derive(Clone, Debug)]
+enum SvgCell {
+    Gap,
+    Grapheme(Ansi, Grapheme, usize),
+}
+
+/// A sink that draws the buffer as an SVG image of a terminal window, for screenshots in docs.
+///
+/// Cells are kept until `finalize`, since the image's size has to be written first. Styles are
+/// read through `AnsiTag` and coloured with a `Palette`; hyperlinks and blinking are not drawn.
+/// Runs of graphemes with the same style are drawn as one `<text>` stretched over their cells, and
+/// underlines and strikethrough are drawn as lines, since renderers disagree on text decorations.
+pub struct SvgSink<W: CharacterWriter, P: WidthProvider = AmbiguityPolicy> {
+    writer: W,
+    width_provider: P,
+    palette: Palette,
+    metrics: SvgMetrics,
+    chrome: bool,
+    title: Option<String>,
+    font_family: String,
+
+    rows: Vec<Vec<SvgCell>>,
+    row: Vec<SvgCell>,
+}
+
+impl<W: CharacterWriter, P: WidthProvider> SvgSink<W, P> {
+    /// `width_provider` must agree with the one the buffer renders with.
+    pub fn new(writer: W, width_provider: P) -> Self {
+        Self {
+            writer,
+            width_provider,
+            palette: Palette::default(),
+            metrics: SvgMetrics::default(),
+            chrome: false,
+            title: None,
+            font_family: String::from("ui-monospace, Menlo, Consolas, monospace"),
+            rows: Vec::new(),
+            row: Vec::new(),
+        }
+    }
+
+    pub fn with_palette(mut self, palette: Palette) -> Self {
+        self.palette = palette;
+        self
+    }
+
+    pub fn with_metrics(mut self, metrics: SvgMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Draws a window title bar with the three traffic-light buttons above the grid.
+    pub fn with_chrome(mut self, chrome: bool) -> Self {
+        self.chrome = chrome;
+        self
+    }
+
+    /// The title shown in the title bar, when `with_chrome` is enabled.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_font_family(mut self, font_family: impl Into<String>) -> Self {
+        self.font_family = font_family.into();
+        self
+    }
+
+    fn render(&self) -> Result<String, core::fmt::Error> {
+        let metrics = self.metrics;
+        let palette = &self.palette;
+
+        let columns = self
+            .rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|cell| match cell {
+                        SvgCell::Gap => 1,
+                        SvgCell::Grapheme(_, _, width) => *width,
+                    })
+                    .sum::<usize>()
+            })
+            .max()
+            .unwrap_or(0);
+
+        let top = if self.chrome { CHROME_HEIGHT } else { 0 } + metrics.padding;
+        let width = columns * metrics.cell_width + 2 * metrics.padding;
+        let height = top + self.rows.len() * metrics.line_height + metrics.padding;
+
+        let mut out = String::new();
+
+        write!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+             viewBox=\"0 0 {width} {height}\" font-size=\"{}\" font-family=\"",
+            metrics.font_size,
+        )?;
+        write_escaped(&mut out, &self.font_family)?;
+        out.push_str("\" xml:space=\"preserve\">");
+
+        write!(out, "<rect width=\"{width}\" height=\"{height}\"")?;
+        if self.chrome {
+            out.push_str(" rx=\"6\"");
+        }
+        out.push_str(" fill=\"");
+        write_hex(&mut out, palette.background)?;
+        out.push_str("\"/>");
+
+        if self.chrome {
+            for (i, fill) in ["#ff5f56", "#ffbd2e", "#27c93f"].into_iter().enumerate() {
+                write!(
+                    out,
+                    "<circle cx=\"{}\" cy=\"{}\" r=\"6\" fill=\"{fill}\"/>",
+                    20 + i * 20,
+                    CHROME_HEIGHT / 2,
+                )?;
+            }
+
+            if let Some(title) = &self.title {
+                write!(
+                    out,
+                    "<text x=\"{}\" y=\"{}\" text-anchor=\"middle\" dominant-baseline=\"middle\" \
+                     fill-opacity=\"0.6\" fill=\"",
+                    width / 2,
+                    CHROME_HEIGHT / 2,
+                )?;
+                write_hex(&mut out, palette.foreground)?;
+                out.push_str("\">");
+                write_escaped(&mut out, title)?;
+                out.push_str("</text>");
+            }
+        }
+
+        for (line, row) in self.rows.iter().enumerate() {
+            let y = top + line * metrics.line_height;
+            let mut column = 0;
+            let mut cells = row.iter().peekable();
+
+            while let Some(cell) = cells.next() {
+                let SvgCell::Grapheme(tag, grapheme, width) = cell else {
+                    column += 1;
+                    continue;
+                };
+
+                // Group the graphemes that share this one's style and width into one `<text>`.
+                let mut text = String::from(grapheme.as_str());
+                let mut count = 1;
+
+                while let Some(SvgCell::Grapheme(next_tag, next, next_width)) = cells.peek() {
+                    if next_tag != tag || next_width != width {
+                        break;
+                    }
+
+                    text.push_str(next.as_str());
+                    count += 1;
+                    cells.next();
+                }
+
+                let run = Run {
+                    x: metrics.padding + column * metrics.cell_width,
+                    y,
+                    width: count * width * metrics.cell_width,
+                    count,
+                };
+
+                column += count * width;
+                self.write_run(&mut out, tag, &text, run)?;
+            }
+        }
+
+        out.push_str("</svg>");
+
+        Ok(out)
+    }
+
+    fn write_run(&self, out: &mut String, tag: &Ansi, text: &str, run: Run) -> core::fmt::Result {
+        let metrics = self.metrics;
+        let palette = &self.palette;
+        let Run { x, y, width, count } = run;
+
+        if let Some(background) = palette.background(tag) {
+            write!(
+                out,
+                "<rect x=\"{x}\" y=\"{y}\" width=\"{width}\" height=\"{}\" fill=\"",
+                metrics.line_height,
+            )?;
+            write_hex(out, background)?;
+            out.push_str("\"/>");
+        }
+
+        if tag.is_concealed() {
+            return Ok(());
+        }
+
+        let foreground = palette.foreground(tag).unwrap_or(palette.foreground);
+        let baseline = y + (metrics.line_height + metrics.font_size * 7 / 10) / 2;
+
+        write!(out, "<text x=\"{x}\" y=\"{baseline}\"")?;
+        if count > 1 {
+            // Keep the glyphs on the grid whatever the font's advance widths are.
+            write!(out, " textLength=\"{width}\"")?;
+        }
+        out.push_str(" fill=\"");
+        write_hex(out, foreground)?;
+        out.push('"');
+
+        match tag.get_intensity() {
+            Intensity::Bold => out.push_str(" font-weight=\"bold\""),
+            Intensity::Faint => out.push_str(" fill-opacity=\"0.5\""),
+            Intensity::Normal => {}
+        }
+
+        if tag.is_italic() {
+            out.push_str(" font-style=\"italic\"");
+        }
+
+        out.push('>');
+        write_escaped(out, text)?;
+        out.push_str("</text>");
+
+        let opacity = if tag.get_intensity() == Intensity::Faint {
+            " stroke-opacity=\"0.5\""
+        } else {
+            ""
+        };
+
+        if let Some(style) = tag.get_underline() {
+            let color = palette.underline(tag).unwrap_or(foreground);
+            let y = baseline + metrics.font_size / 8 + 1;
+            let end = x + width;
+
+            let line = |out: &mut String, y: usize, dash: &str| -> core::fmt::Result {
+                write!(
+                    out,
+                    "<line x1=\"{x}\" y1=\"{y}\" x2=\"{end}\" y2=\"{y}\"{dash}{opacity} stroke=\""
+                )?;
+                write_hex(out, color)?;
+                out.push_str("\"/>");
+                Ok(())
+            };
+
+            match style {
+                UnderlineStyle::Single => line(out, y, "")?,
+                UnderlineStyle::Double => {
+                    line(out, y, "")?;
+                    line(out, y + 2, "")?;
+                }
+                UnderlineStyle::Dotted => line(out, y, " stroke-dasharray=\"1 2\"")?,
+                UnderlineStyle::Dashed => line(out, y, " stroke-dasharray=\"4 2\"")?,
+                UnderlineStyle::Curly => {
+                    // A zigzag with one peak per half cell.
+                    let step = (metrics.cell_width / 2).max(1);
+                    write!(out, "<path d=\"M{x} {}", y + 1)?;
+                    for (i, point) in (x + step..=end).step_by(step).enumerate() {
+                        write!(out, " L{point} {}", if i % 2 == 0 { y - 1 } else { y + 1 })?;
+                    }
+                    if (end - x) % step != 0 {
+                        write!(out, " L{end} {y}")?;
+                    }
+                    write!(out, "\" fill=\"none\"{opacity} stroke=\"")?;
+                    write_hex(out, color)?;
+                    out.push_str("\"/>");
+                }
+            }
+        }
+
+        if tag.is_strikethrough() {
+            let y = baseline - metrics.font_size * 3 / 10;
+
+            write!(
+                out,
+                "<line x1=\"{x}\" y1=\"{y}\" x2=\"{}\" y2=\"{y}\"{opacity} stroke=\"",
+                x + width,
+            )?;
+            write_hex(out, foreground)?;
+            out.push_str("\"/>");
+        }
+
+        Ok(())
+    }
+}
+
+/// Where a run of graphemes with the same style is drawn, in pixels.
+#[derive(Clone, Copy)]
+struct Run {
+    x: usize,
+    y: usize,
+    width: usize,
+
+    /// The number of graphemes in the run.
+    count: usize,
+}
+
+impl<W: CharacterWriter, P: WidthProvider, T: Tag + AnsiDeref> TagSink<T> for SvgSink<W, P> {
+    type Result = Result<W, PlaintextError<W::Error>>;
+
+    fn append(&mut self, grapheme: &gph, tag: T) -> ControlFlow<()> {
+        let width = grapheme.width(&self.width_provider);
+
+        self.row.push(SvgCell::Grapheme(
+            Ansi::from_tag(tag),
+            Grapheme::from(grapheme),
+            width,
+        ));
+
+        ControlFlow::Continue(())
+    }
+
+    fn gap(&mut self) -> ControlFlow<()> {
+        self.row.push(SvgCell::Gap);
+
+        ControlFlow::Continue(())
+    }
+
+    fn finalize_line(&mut self) -> ControlFlow<()> {
+        self.rows.push(core::mem::take(&mut self.row));
+
+        ControlFlow::Continue(())
+    }
+
+    fn finalize(mut self) -> Self::Result {
+        // Formatting into a `String` cannot fail.
+        let svg = self.render().unwrap_or_default();
+
+        self.writer
+            .write_str(&svg)
+            .map_err(PlaintextError::Writer)?;
+
+        Ok(self.writer)
+    }
+}
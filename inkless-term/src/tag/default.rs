@@ -308,7 +308,65 @@ impl Ansi {
 }
 
 // This makes `Ansi` usable as a tag in your render system.
-impl Tag for Ansi {}
+impl Tag for Ansi {
+    /// Fields left unset (no colour, normal intensity, attributes off) inherit from `parent`.
+    ///
+    /// The foreground, background and underline colours are each inherited as a whole: a child
+    /// that sets any representation of a colour, e.g. only `bright_red`, replaces all of the
+    /// parent's representations of it, so a terminal cannot pick up the parent's colour instead.
+    fn merge(self, parent: &Self) -> Self {
+        let fg = if self.ansi8_fg.is_some()
+            || self.ansi16_fg.is_some()
+            || self.ansi256_fg.is_some()
+            || self.true_fg.is_some()
+        {
+            &self
+        } else {
+            parent
+        };
+
+        let bg = if self.ansi8_bg.is_some()
+            || self.ansi16_bg.is_some()
+            || self.ansi256_bg.is_some()
+            || self.true_bg.is_some()
+        {
+            &self
+        } else {
+            parent
+        };
+
+        let underline_color = if self.ansi256_underline.is_some() || self.true_underline.is_some() {
+            &self
+        } else {
+            parent
+        };
+
+        Self {
+            ansi8_fg: fg.ansi8_fg,
+            ansi16_fg: fg.ansi16_fg,
+            ansi256_fg: fg.ansi256_fg,
+            true_fg: fg.true_fg,
+
+            ansi8_bg: bg.ansi8_bg,
+            ansi16_bg: bg.ansi16_bg,
+            ansi256_bg: bg.ansi256_bg,
+            true_bg: bg.true_bg,
+
+            underline: self.underline.or(parent.underline),
+            ansi256_underline: underline_color.ansi256_underline,
+            true_underline: underline_color.true_underline,
+
+            intensity: match self.intensity {
+                Intensity::Normal => parent.intensity,
+                intensity => intensity,
+            },
+            blink: self.blink.or(parent.blink),
+            italic: self.italic || parent.italic,
+            concealed: self.concealed || parent.concealed,
+            strikethrough: self.strikethrough || parent.strikethrough,
+        }
+    }
+}
 
 impl AnsiTag for Ansi {
     fn get_ansi8_foreground_color(&self) -> Option<Ansi8Color> {
@@ -417,3 +475,25 @@ impl Ansi {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn merge_inherits_each_colour_as_a_whole() {
+        let parent = Ansi::new()
+            .fg_true(TrueColor(1, 2, 3))
+            .bg_true(TrueColor(4, 5, 6))
+            .bold();
+        let merged = Ansi::new().bright_red().merge(&parent);
+
+        assert_eq!(
+            merged.get_ansi16_foreground_color(),
+            Some(Ansi16Color::Bright(Ansi8Color::Red))
+        );
+        assert_eq!(merged.get_true_foreground_color(), None);
+        assert_eq!(merged.get_true_background_color(), Some(TrueColor(4, 5, 6)));
+        assert_eq!(merged.get_intensity(), Intensity::Bold);
+    }
+}
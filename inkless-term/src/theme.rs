@@ -0,0 +1,440 @@
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+
+use inkless_core::{tag::NamedTag, theme::RuntimeTheme};
+use thiserror::Error;
+
+use crate::{
+    styles::{Ansi8Color, Ansi16Color, BlinkSpeed, TrueColor, UnderlineStyle},
+    tag::default::Ansi,
+};
+
+/// A theme loaded at runtime, mapping the names of `NamedTag`s to `Ansi` styles.
+///
+/// The source has one definition per line, with `#` starting a comment:
+///
+/// ```text
+/// # Errors stand out.
+/// error = "bold red on default"
+/// number = "cyan"
+/// number.separator = "faint"
+/// link = "underline #4078f2"
+/// ```
+///
+/// A style is a list of words:
+///
+/// - attributes: `bold`, `faint`, `italic`, `underline`, `double-underline`, `curly-underline`,
+///   `dotted-underline`, `dashed-underline`, `blink`, `rapid-blink`, `hidden`, `strikethrough`;
+/// - a foreground colour: `black`, `red`, `green`, `yellow`, `blue`, `magenta`, `cyan`, `white`,
+///   their `bright-` variants, a `#rrggbb` true colour, or `default` to leave it unset;
+/// - `on` followed by a background colour, in the same forms.
+///
+/// A style sets at most one foreground and one background colour.
+///
+/// Tags whose name is not defined fall back to their parent, so `number.separator` uses `number`
+/// when it has no definition of its own, and to no style at all when no parent matches.
+#[derive(Clone, Debug, Default)]
+pub struct ConfigTheme {
+    styles: BTreeMap<String, Ansi>,
+}
+
+/// Returned by `ConfigTheme::parse`, pointing at the offending text.
+///
+/// `line` and `column` are 1-based, and the column counts characters.
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+#[error("line {line}, column {column}: {kind}")]
+pub struct ConfigThemeError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ConfigThemeErrorKind,
+}
+
+#[derive(Error, Clone, Debug, PartialEq, Eq)]
+pub enum ConfigThemeErrorKind {
+    #[error("expected a tag name")]
+    ExpectedName,
+
+    #[error("`{0}` is not a valid tag name")]
+    InvalidName(String),
+
+    #[error("expected `=` after the tag name")]
+    ExpectedEquals,
+
+    #[error("expected a quoted style after `=`")]
+    ExpectedStyle,
+
+    #[error("the style is missing its closing quote")]
+    UnterminatedStyle,
+
+    #[error("unexpected text after the style")]
+    TrailingCharacters,
+
+    #[error("`{0}` is not a style attribute or colour")]
+    UnknownStyle(String),
+
+    #[error("`{0}` is not a colour")]
+    UnknownColor(String),
+
+    #[error("expected a colour after `on`")]
+    ExpectedBackground,
+
+    #[error("the foreground colour is already set")]
+    DuplicateForeground,
+
+    #[error("the background colour is already set")]
+    DuplicateBackground,
+
+    #[error("`{0}` is defined more than once")]
+    DuplicateName(String),
+}
+
+impl ConfigTheme {
+    pub fn parse(source: &str) -> Result<Self, ConfigThemeError> {
+        let mut theme = Self::default();
+
+        for (index, line) in source.lines().enumerate() {
+            theme.parse_line(line, index + 1)?;
+        }
+
+        Ok(theme)
+    }
+
+    /// Returns the style for `name`, or for its nearest defined parent.
+    pub fn get(&self, name: &str) -> Option<Ansi> {
+        let mut name = name;
+
+        loop {
+            if let Some(style) = self.styles.get(name) {
+                return Some(*style);
+            }
+
+            name = name.rsplit_once('.')?.0;
+        }
+    }
+
+    fn parse_line(&mut self, line: &str, number: usize) -> Result<(), ConfigThemeError> {
+        let error = |offset: usize, kind| ConfigThemeError {
+            line: number,
+            column: line[..offset].chars().count() + 1,
+            kind,
+        };
+
+        let name_start = skip_whitespace(line, 0);
+        let rest = &line[name_start..];
+
+        if rest.is_empty() || rest.starts_with('#') {
+            return Ok(());
+        }
+
+        let name_end = name_start
+            + rest
+                .find(|c: char| !(c.is_alphanumeric() || matches!(c, '_' | '-' | '.')))
+                .unwrap_or(rest.len());
+        let name = &line[name_start..name_end];
+
+        if name.is_empty() {
+            return Err(error(name_start, ConfigThemeErrorKind::ExpectedName));
+        }
+
+        if name.split('.').any(str::is_empty) {
+            return Err(error(
+                name_start,
+                ConfigThemeErrorKind::InvalidName(name.to_string()),
+            ));
+        }
+
+        let equals = skip_whitespace(line, name_end);
+
+        if !line[equals..].starts_with('=') {
+            return Err(error(equals, ConfigThemeErrorKind::ExpectedEquals));
+        }
+
+        let quote = skip_whitespace(line, equals + 1);
+
+        if !line[quote..].starts_with('"') {
+            return Err(error(quote, ConfigThemeErrorKind::ExpectedStyle));
+        }
+
+        let style_start = quote + 1;
+        let style_end = match line[style_start..].find('"') {
+            Some(length) => style_start + length,
+            None => return Err(error(quote, ConfigThemeErrorKind::UnterminatedStyle)),
+        };
+
+        let trailing = skip_whitespace(line, style_end + 1);
+
+        if trailing < line.len() && !line[trailing..].starts_with('#') {
+            return Err(error(trailing, ConfigThemeErrorKind::TrailingCharacters));
+        }
+
+        let style = parse_style(&line[style_start..style_end])
+            .map_err(|(offset, kind)| error(style_start + offset, kind))?;
+
+        if self.styles.contains_key(name) {
+            return Err(error(
+                name_start,
+                ConfigThemeErrorKind::DuplicateName(name.to_string()),
+            ));
+        }
+
+        self.styles.insert(name.to_string(), style);
+
+        Ok(())
+    }
+}
+
+impl<T: NamedTag> RuntimeTheme<T> for ConfigTheme {
+    type Result = Ansi;
+
+    fn translate(&self, from: T) -> Self::Result {
+        self.get(from.name()).unwrap_or_default()
+    }
+}
+
+fn skip_whitespace(line: &str, offset: usize) -> usize {
+    line[offset..]
+        .find(|c: char| !c.is_whitespace())
+        .map_or(line.len(), |length| offset + length)
+}
+
+/// Parses the words of a style, returning the byte offset of the word at fault on failure.
+fn parse_style(style: &str) -> Result<Ansi, (usize, ConfigThemeErrorKind)> {
+    let offset_of = |word: &str| word.as_ptr() as usize - style.as_ptr() as usize;
+
+    let mut ansi = Ansi::new();
+    let mut words = style.split_whitespace();
+    let mut foreground = false;
+    let mut background = false;
+
+    while let Some(word) = words.next() {
+        ansi = match word {
+            "bold" => ansi.bold(),
+            "faint" => ansi.faint(),
+            "italic" => ansi.italic(true),
+            "underline" => ansi.underline(UnderlineStyle::Single),
+            "double-underline" => ansi.underline(UnderlineStyle::Double),
+            "curly-underline" => ansi.underline(UnderlineStyle::Curly),
+            "dotted-underline" => ansi.underline(UnderlineStyle::Dotted),
+            "dashed-underline" => ansi.underline(UnderlineStyle::Dashed),
+            "blink" => ansi.blink(BlinkSpeed::Slow),
+            "rapid-blink" => ansi.blink(BlinkSpeed::Rapid),
+            "hidden" => ansi.concealed(true),
+            "strikethrough" => ansi.strikethrough(true),
+
+            "on" => {
+                let Some(word) = words.next() else {
+                    return Err((style.len(), ConfigThemeErrorKind::ExpectedBackground));
+                };
+
+                match Color::parse(word) {
+                    Some(_) if background => {
+                        return Err((offset_of(word), ConfigThemeErrorKind::DuplicateBackground));
+                    }
+                    Some(color) => {
+                        background = true;
+                        color.background(ansi)
+                    }
+                    None => {
+                        return Err((
+                            offset_of(word),
+                            ConfigThemeErrorKind::UnknownColor(word.to_string()),
+                        ));
+                    }
+                }
+            }
+
+            _ => match Color::parse(word) {
+                Some(_) if foreground => {
+                    return Err((offset_of(word), ConfigThemeErrorKind::DuplicateForeground));
+                }
+                Some(color) => {
+                    foreground = true;
+                    color.foreground(ansi)
+                }
+                None => {
+                    return Err((
+                        offset_of(word),
+                        ConfigThemeErrorKind::UnknownStyle(word.to_string()),
+                    ));
+                }
+            },
+        };
+    }
+
+    Ok(ansi)
+}
+
+enum Color {
+    Default,
+    Normal(Ansi8Color),
+    Bright(Ansi8Color),
+    True(TrueColor),
+}
+
+impl Color {
+    fn parse(word: &str) -> Option<Self> {
+        if word == "default" {
+            return Some(Self::Default);
+        }
+
+        if let Some(hex) = word.strip_prefix('#') {
+            // `from_str_radix` accepts a leading `+`, so check the digits first.
+            if hex.len() != 6 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return None;
+            }
+
+            let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+
+            return Some(Self::True(TrueColor(channel(0)?, channel(2)?, channel(4)?)));
+        }
+
+        let (bright, name) = match word.strip_prefix("bright-") {
+            Some(name) => (true, name),
+            None => (false, word),
+        };
+
+        let color = match name {
+            "black" => Ansi8Color::Black,
+            "red" => Ansi8Color::Red,
+            "green" => Ansi8Color::Green,
+            "yellow" => Ansi8Color::Yellow,
+            "blue" => Ansi8Color::Blue,
+            "magenta" => Ansi8Color::Magenta,
+            "cyan" => Ansi8Color::Cyan,
+            "white" => Ansi8Color::White,
+            _ => return None,
+        };
+
+        Some(if bright {
+            Self::Bright(color)
+        } else {
+            Self::Normal(color)
+        })
+    }
+
+    fn foreground(self, ansi: Ansi) -> Ansi {
+        match self {
+            Self::Default => ansi,
+            Self::Normal(color) => ansi.fg8(color).fg16(Ansi16Color::Normal(color)),
+            Self::Bright(color) => ansi.fg16(Ansi16Color::Bright(color)),
+            Self::True(color) => ansi.fg_true(color),
+        }
+    }
+
+    fn background(self, ansi: Ansi) -> Ansi {
+        match self {
+            Self::Default => ansi,
+            Self::Normal(color) => ansi.bg8(color).bg16(Ansi16Color::Normal(color)),
+            Self::Bright(color) => ansi.bg16(Ansi16Color::Bright(color)),
+            Self::True(color) => ansi.bg_true(color),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{styles::Intensity, tag::AnsiTag};
+
+    fn error(source: &str) -> (usize, usize, ConfigThemeErrorKind) {
+        let error = ConfigTheme::parse(source).unwrap_err();
+        (error.line, error.column, error.kind)
+    }
+
+    #[test]
+    fn parses_styles_and_falls_back_to_parents() {
+        let theme = ConfigTheme::parse(
+            "# Numbers\n\
+             number = \"bright-cyan on #102030\"  # trailing comment\n\
+             \n\
+             number.separator = \"faint default\"\n",
+        )
+        .unwrap();
+
+        let number = theme.get("number.digit").unwrap();
+        assert_eq!(
+            number.get_ansi16_foreground_color(),
+            Some(Ansi16Color::Bright(Ansi8Color::Cyan))
+        );
+        assert_eq!(
+            number.get_true_background_color(),
+            Some(TrueColor(0x10, 0x20, 0x30))
+        );
+
+        let separator = theme.get("number.separator").unwrap();
+        assert_eq!(separator.get_intensity(), Intensity::Faint);
+        assert_eq!(separator.get_ansi16_foreground_color(), None);
+
+        assert_eq!(theme.get("error"), None);
+    }
+
+    #[test]
+    fn rejects_hex_colours_with_signs() {
+        assert_eq!(
+            error("a = \"#+12345\""),
+            (
+                1,
+                6,
+                ConfigThemeErrorKind::UnknownStyle("#+12345".to_string())
+            )
+        );
+        assert_eq!(
+            error("a = \"on #12+345\""),
+            (
+                1,
+                9,
+                ConfigThemeErrorKind::UnknownColor("#12+345".to_string())
+            )
+        );
+    }
+
+    #[test]
+    fn rejects_a_second_colour() {
+        assert_eq!(
+            error("a = \"red #ff0000\""),
+            (1, 10, ConfigThemeErrorKind::DuplicateForeground)
+        );
+        assert_eq!(
+            error("a = \"on red bold on blue\""),
+            (1, 21, ConfigThemeErrorKind::DuplicateBackground)
+        );
+    }
+
+    #[test]
+    fn reports_lines_and_columns() {
+        assert_eq!(
+            error("ok = \"bold\"\n  = \"red\""),
+            (2, 3, ConfigThemeErrorKind::ExpectedName)
+        );
+        assert_eq!(
+            error("a..b = \"red\""),
+            (1, 1, ConfigThemeErrorKind::InvalidName("a..b".to_string()))
+        );
+        assert_eq!(
+            error("a \"red\""),
+            (1, 3, ConfigThemeErrorKind::ExpectedEquals)
+        );
+        assert_eq!(
+            error("a = red"),
+            (1, 5, ConfigThemeErrorKind::ExpectedStyle)
+        );
+        assert_eq!(
+            error("é = \"red"),
+            (1, 5, ConfigThemeErrorKind::UnterminatedStyle)
+        );
+        assert_eq!(
+            error("a = \"red\" x"),
+            (1, 11, ConfigThemeErrorKind::TrailingCharacters)
+        );
+        assert_eq!(
+            error("a = \"bold on\""),
+            (1, 13, ConfigThemeErrorKind::ExpectedBackground)
+        );
+        assert_eq!(
+            error("a = \"red\"\na = \"blue\""),
+            (2, 1, ConfigThemeErrorKind::DuplicateName("a".to_string()))
+        );
+    }
+}
@@ -97,34 +97,7 @@ pub fn write_ansi16_fg<W: CharacterWriter>(
     writer.write_str(seq)
 }
 
-fn write_u8_dec<W: CharacterWriter>(writer: &mut W, mut n: u8) -> Result<(), W::Error> {
-    let mut buf = [0u8; 3];
-    let mut len = 0;
-
-    if n == 0 {
-        buf[0] = b'0';
-        len = 1;
-    } else {
-        // First write digits in reverse into `tmp`
-        let mut tmp = [0u8; 3];
-        while n > 0 {
-            tmp[len] = b'0' + (n % 10) as u8;
-            len += 1;
-            n /= 10;
-        }
-
-        // Now reverse them into `buf` so they're in the right order
-        for i in 0..len {
-            buf[i] = tmp[len - 1 - i];
-        }
-    }
-
-    // &buf[..len] contains only '0'..='9'
-    let s = str::from_utf8(&buf[..len]).unwrap();
-    writer.write_str(s)
-}
-
-fn ansi8_index(color: Ansi8Color) -> u8 {
+pub(crate) fn ansi8_index(color: Ansi8Color) -> u8 {
     match color {
         Ansi8Color::Black => 0,
         Ansi8Color::Red => 1,
@@ -162,7 +135,7 @@ pub fn write_ansi256_fg<W: CharacterWriter>(
     let idx = ansi256_index(color);
 
     writer.write_str("\x1b[38;5;")?;
-    write_u8_dec(writer, idx)?;
+    write_dec(writer, idx)?;
     writer.write_str("m")
 }
 
@@ -171,11 +144,11 @@ pub fn write_truecolor_fg<W: CharacterWriter>(
     TrueColor(r, g, b): TrueColor,
 ) -> Result<(), W::Error> {
     writer.write_str("\x1b[38;2;")?;
-    write_u8_dec(writer, r)?;
+    write_dec(writer, r)?;
     writer.write_str(";")?;
-    write_u8_dec(writer, g)?;
+    write_dec(writer, g)?;
     writer.write_str(";")?;
-    write_u8_dec(writer, b)?;
+    write_dec(writer, b)?;
     writer.write_str("m")
 }
 
@@ -232,7 +205,7 @@ pub fn write_ansi256_bg<W: CharacterWriter>(
     let idx = ansi256_index(color); // same logic as fg
 
     writer.write_str("\x1b[48;5;")?;
-    write_u8_dec(writer, idx)?;
+    write_dec(writer, idx)?;
     writer.write_str("m")
 }
 
@@ -241,11 +214,11 @@ pub fn write_truecolor_bg<W: CharacterWriter>(
     TrueColor(r, g, b): TrueColor,
 ) -> Result<(), W::Error> {
     writer.write_str("\x1b[48;2;")?;
-    write_u8_dec(writer, r)?;
+    write_dec(writer, r)?;
     writer.write_str(";")?;
-    write_u8_dec(writer, g)?;
+    write_dec(writer, g)?;
     writer.write_str(";")?;
-    write_u8_dec(writer, b)?;
+    write_dec(writer, b)?;
     writer.write_str("m")
 }
 
@@ -256,7 +229,7 @@ pub fn write_ansi256_underline_color<W: CharacterWriter>(
     let idx = ansi256_index(color); // same helper you use for fg/bg
 
     writer.write_str("\x1b[58;5;")?;
-    write_u8_dec(writer, idx)?;
+    write_dec(writer, idx)?;
     writer.write_str("m")
 }
 
@@ -265,10 +238,55 @@ pub fn write_truecolor_underline_color<W: CharacterWriter>(
     TrueColor(r, g, b): TrueColor,
 ) -> Result<(), W::Error> {
     writer.write_str("\x1b[58;2;")?;
-    write_u8_dec(writer, r)?;
+    write_dec(writer, r)?;
     writer.write_str(";")?;
-    write_u8_dec(writer, g)?;
+    write_dec(writer, g)?;
     writer.write_str(";")?;
-    write_u8_dec(writer, b)?;
+    write_dec(writer, b)?;
     writer.write_str("m")
 }
+
+/// Writes `n` in decimal, for the numeric parameters of escape sequences.
+fn write_dec<W: CharacterWriter>(writer: &mut W, n: impl Into<usize>) -> Result<(), W::Error> {
+    let mut n = n.into();
+    let mut buf = [0u8; 20];
+    let mut start = buf.len();
+
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+
+        if n == 0 {
+            break;
+        }
+    }
+
+    // &buf[start..] contains only '0'..='9'
+    let s = str::from_utf8(&buf[start..]).unwrap();
+    writer.write_str(s)
+}
+
+/// Moves the cursor up `count` lines (CUU), keeping its column.
+pub fn write_cursor_up<W: CharacterWriter>(writer: &mut W, count: usize) -> Result<(), W::Error> {
+    writer.write_str("\x1b[")?;
+    write_dec(writer, count)?;
+    writer.write_str("A")
+}
+
+/// Moves the cursor down `count` lines (CUD), keeping its column. This never scrolls the terminal.
+pub fn write_cursor_down<W: CharacterWriter>(writer: &mut W, count: usize) -> Result<(), W::Error> {
+    writer.write_str("\x1b[")?;
+    write_dec(writer, count)?;
+    writer.write_str("B")
+}
+
+/// Moves the cursor to the zero-based `column` of the current line (CHA).
+pub fn write_cursor_column<W: CharacterWriter>(
+    writer: &mut W,
+    column: usize,
+) -> Result<(), W::Error> {
+    writer.write_str("\x1b[")?;
+    write_dec(writer, column + 1)?;
+    writer.write_str("G")
+}
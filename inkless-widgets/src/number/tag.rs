@@ -1,4 +1,4 @@
-use inkless_core::tag::{Tag, untagged::Untagged};
+use inkless_core::tag::{NamedTag, Tag, untagged::Untagged};
 
 #[derive(Clone, Copy, Debug)]
 pub enum NumberTag {
@@ -10,6 +10,17 @@ pub enum NumberTag {
 
 impl Tag for NumberTag {}
 
+impl NamedTag for NumberTag {
+    fn name(&self) -> &str {
+        match self {
+            Self::Minus => "number.minus",
+            Self::Prefix { .. } => "number.prefix",
+            Self::Digit { .. } => "number.digit",
+            Self::Separator { .. } => "number.separator",
+        }
+    }
+}
+
 impl From<NumberTag> for Untagged {
     fn from(_: NumberTag) -> Self {
         Untagged
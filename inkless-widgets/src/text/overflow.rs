@@ -1,3 +1,5 @@
+use inkless_core::{canvas::environment::EnvironmentKey, grapheme::gph};
+
 /// Determines where the ellipsis (`…`) should appear when text
 /// is truncated due to overflow.
 ///
@@ -44,3 +46,12 @@ pub enum Overflow {
     /// Rendering will stop and return an error from the `TagSink`/writer.
     Error,
 }
+
+/// The environment key for the grapheme `Overflow::Ellipsis` draws, which is `…` when unset.
+///
+/// Set it with `WithEnvironment` to fall back to ASCII, e.g. `~`, on terminals that cannot draw `…`.
+pub struct EllipsisGlyph;
+
+impl EnvironmentKey for EllipsisGlyph {
+    type Value = &'static gph;
+}
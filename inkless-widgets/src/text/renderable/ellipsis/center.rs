@@ -1,10 +1,11 @@
-use inkless_core::{canvas::Canvas, render_position::RenderPosition, tag::Tag};
-use inkless_macros::gph;
+use inkless_core::{
+    canvas::Canvas, diagnostics::DiagnosticKind, render_position::RenderPosition, tag::Tag,
+};
 
 use crate::text::renderable::{
     TextTag,
     ellipsis::{
-        center_candidate_fits, count_graphemes, draw_center, draw_line_full,
+        center_candidate_fits, count_graphemes, draw_center, draw_line_full, ellipsis_glyph,
         line_fits_without_ellipsis, prefix_plus_ellipsis_fits,
     },
 };
@@ -15,7 +16,7 @@ pub fn render_segment_ellipsis_center<T1: Tag + Clone, T2: Tag, T3: Tag + From<T
     canvas: &mut dyn Canvas<T3>,
     start: RenderPosition,
 ) {
-    let ell = gph!("…");
+    let ell = ellipsis_glyph(canvas);
 
     for (i, line) in text.split('\n').enumerate() {
         if i > 0 {
@@ -30,6 +31,8 @@ pub fn render_segment_ellipsis_center<T1: Tag + Clone, T2: Tag, T3: Tag + From<T
             continue;
         }
 
+        canvas.report(DiagnosticKind::Truncated);
+
         // Step 1: get the maximal prefix that fits with an ellipsis (right-style).
         let mut lo = 0usize;
         let mut hi = total;
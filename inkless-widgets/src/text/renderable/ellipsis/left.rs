@@ -1,11 +1,12 @@
-use inkless_core::{canvas::Canvas, render_position::RenderPosition, tag::Tag};
-use inkless_macros::gph;
+use inkless_core::{
+    canvas::Canvas, diagnostics::DiagnosticKind, render_position::RenderPosition, tag::Tag,
+};
 
 use crate::text::renderable::{
     TextTag,
     ellipsis::{
-        count_graphemes, draw_ellipsis_plus_suffix, draw_line_full, ellipsis_plus_suffix_fits,
-        line_fits_without_ellipsis,
+        count_graphemes, draw_ellipsis_plus_suffix, draw_line_full, ellipsis_glyph,
+        ellipsis_plus_suffix_fits, line_fits_without_ellipsis,
     },
 };
 
@@ -15,7 +16,7 @@ pub fn render_segment_ellipsis_left<T1: Tag + Clone, T2: Tag, T3: Tag + From<Tex
     canvas: &mut dyn Canvas<T3>,
     start: RenderPosition,
 ) {
-    let ell = gph!("…");
+    let ell = ellipsis_glyph(canvas);
 
     for (i, line) in text.split('\n').enumerate() {
         if i > 0 {
@@ -30,6 +31,8 @@ pub fn render_segment_ellipsis_left<T1: Tag + Clone, T2: Tag, T3: Tag + From<Tex
             continue;
         }
 
+        canvas.report(DiagnosticKind::Truncated);
+
         // Binary search the longest suffix that fits with a leading ellipsis.
         let mut lo = 0usize;
         let mut hi = total;
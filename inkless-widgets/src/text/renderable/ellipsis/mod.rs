@@ -6,7 +6,7 @@ use inkless_core::{canvas::Canvas, grapheme::gph, render_position::RenderPositio
 use inkless_macros::gph;
 
 use crate::text::{
-    overflow::EllipsisPosition,
+    overflow::{EllipsisGlyph, EllipsisPosition},
     renderable::{
         TextTag,
         ellipsis::{
@@ -30,6 +30,11 @@ pub fn render_segment_ellipsis<T1: Tag + Clone, T2: Tag, T3: Tag + From<TextTag<
     }
 }
 
+/// Returns the grapheme to draw as the ellipsis, as set through the `EllipsisGlyph` environment key.
+fn ellipsis_glyph<T: Tag>(canvas: &dyn Canvas<T>) -> &'static gph {
+    canvas.env::<EllipsisGlyph>().copied().unwrap_or(gph!("…"))
+}
+
 pub(self) fn count_graphemes(text: &str) -> usize {
     let mut n = 0;
     for _ in gph::from_str(text) {
@@ -200,7 +205,7 @@ pub(self) fn draw_prefix_plus_ellipsis<
     tag: &T1,
     canvas: &mut dyn Canvas<T3>,
 ) {
-    let ell = gph!("…");
+    let ell = ellipsis_glyph(canvas);
 
     let mut idx = 0;
     for grapheme in gph::from_str(line) {
@@ -229,7 +234,7 @@ pub(self) fn draw_ellipsis_plus_suffix<
     tag: &T1,
     canvas: &mut dyn Canvas<T3>,
 ) {
-    let ell = gph!("…");
+    let ell = ellipsis_glyph(canvas);
     let _ = canvas.set_gph(ell, TextTag::Ellipsis(EllipsisPosition::Left).into());
 
     let skip = total_graphemes.saturating_sub(suffix_len);
@@ -253,7 +258,7 @@ pub(super) fn draw_center<T1: Tag + Clone, T2: Tag, T3: Tag + From<TextTag<T1, T
     tag: &T1,
     canvas: &mut dyn Canvas<T3>,
 ) {
-    let ell = gph!("…");
+    let ell = ellipsis_glyph(canvas);
 
     // Prefix
     let mut idx = 0;
@@ -1,11 +1,12 @@
-use inkless_core::{canvas::Canvas, render_position::RenderPosition, tag::Tag};
-use inkless_macros::gph;
+use inkless_core::{
+    canvas::Canvas, diagnostics::DiagnosticKind, render_position::RenderPosition, tag::Tag,
+};
 
 use crate::text::renderable::{
     TextTag,
     ellipsis::{
-        count_graphemes, draw_line_full, draw_prefix_plus_ellipsis, line_fits_without_ellipsis,
-        prefix_plus_ellipsis_fits,
+        count_graphemes, draw_line_full, draw_prefix_plus_ellipsis, ellipsis_glyph,
+        line_fits_without_ellipsis, prefix_plus_ellipsis_fits,
     },
 };
 
@@ -15,7 +16,7 @@ pub fn render_segment_ellipsis_right<T1: Tag + Clone, T2: Tag, T3: Tag + From<Te
     canvas: &mut dyn Canvas<T3>,
     start: RenderPosition,
 ) {
-    let ell = gph!("…");
+    let ell = ellipsis_glyph(canvas);
 
     for (i, line) in text.split('\n').enumerate() {
         if i > 0 {
@@ -31,6 +32,8 @@ pub fn render_segment_ellipsis_right<T1: Tag + Clone, T2: Tag, T3: Tag + From<Te
             continue;
         }
 
+        canvas.report(DiagnosticKind::Truncated);
+
         // Binary search the longest prefix that fits with an ellipsis.
         let mut lo = 0usize;
         let mut hi = total;
@@ -3,9 +3,15 @@ pub mod ellipsis;
 pub mod grapheme_wrap;
 pub mod word_wrap;
 
+use core::any::{Any, TypeId};
+
 use inkless_core::{
-    canvas::{Canvas, into::IntoCanvas, summary::CanvasSummary},
-    grapheme::gph,
+    canvas::{
+        Canvas, adapter::AdaptedRenderable, into::IntoCanvas, summary::CanvasSummary,
+        tab_stops::TabStops,
+    },
+    diagnostics::DiagnosticKind,
+    grapheme::{gph, width::WidthProvider},
     render_position::RenderPosition,
     renderable::{Renderable, RenderableError},
     tag::Tag,
@@ -22,25 +28,61 @@ use crate::text::{
     tag::TextTag,
 };
 
-struct OverflowError;
+/// Returned by `Text` with `Overflow::Error` when a segment does not fit.
+///
+/// `line` and `column` are where the grapheme that did not fit would have gone, relative to the
+/// position the text started at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OverflowError {
+    pub line: usize,
+    pub column: usize,
+    pub segment_index: usize,
+}
+
+impl OverflowError {
+    fn at(start: RenderPosition, position: RenderPosition, segment_index: usize) -> Self {
+        Self {
+            line: position.line().saturating_sub(start.line()),
+            column: position.column().saturating_sub(start.column()),
+            segment_index,
+        }
+    }
 
-impl core::error::Error for OverflowError {
-    fn description(&self) -> &str {
-        "OverflowError: The text overflowed the canvas, and `Overflow::Error` was selected."
+    /// Without `alloc` the location cannot be carried, so a static error is returned instead.
+    fn into_renderable_error(self) -> RenderableError {
+        #[cfg(feature = "alloc")]
+        return RenderableError::new(self);
+
+        #[cfg(not(feature = "alloc"))]
+        return RenderableError::from_static(&UnlocatedOverflowError);
     }
 }
 
+impl core::error::Error for OverflowError {}
+
 impl core::fmt::Display for OverflowError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str(
-            "OverflowError: The text overflowed the canvas, and `Overflow::Error` was selected.",
+        write!(
+            f,
+            "OverflowError: Segment {} overflowed the canvas at line {}, column {}, and `Overflow::Error` was selected.",
+            self.segment_index, self.line, self.column
         )
     }
 }
 
-impl core::fmt::Debug for OverflowError {
+#[cfg(not(feature = "alloc"))]
+#[derive(Debug)]
+struct UnlocatedOverflowError;
+
+#[cfg(not(feature = "alloc"))]
+impl core::error::Error for UnlocatedOverflowError {}
+
+#[cfg(not(feature = "alloc"))]
+impl core::fmt::Display for UnlocatedOverflowError {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        f.write_str("OverflowError")
+        f.write_str(
+            "OverflowError: The text overflowed the canvas, and `Overflow::Error` was selected.",
+        )
     }
 }
 
@@ -53,22 +95,39 @@ impl<T1: Tag + Clone, S: SegmentStore<T1> + SegmentStoreFetch<T1, T3>, T3: Tag>
     ) -> Result<(), RenderableError> {
         let start = canvas.get_position();
 
+        // The tag of the last segment, which the components after it are written within.
+        let mut parent: Option<&T1> = None;
+
         for i in 0..self.segments.len() {
             let segment = match self.segments.get(i) {
                 Some(s) => s,
                 None => continue,
             };
 
+            if let TextSegment::Segment(_, tag) = segment {
+                parent = Some(tag);
+            }
+
             match segment {
                 TextSegment::Renderable(r) => {
-                    // Delegate to nested renderable as before.
-                    IntoCanvas::new(canvas).write(r)?;
+                    // Delegate to nested renderable, within the segment before it.
+                    match parent {
+                        Some(tag) => canvas.write_within(
+                            TextTag::Segment(tag.clone()),
+                            &AdaptedRenderable::new(r, |canvas, renderable| {
+                                renderable.render_into(&mut IntoCanvas::new(canvas))
+                            }),
+                        )?,
+                        None => IntoCanvas::new(canvas).write(r)?,
+                    };
                 }
                 TextSegment::Segment(text, tag) => {
                     match &self.overflow_behavior {
                         Overflow::Clip => {
-                            // we don't care when it overflows
-                            let _ = render_segment_clip(text, tag, canvas, start);
+                            // Clipping is expected, but still worth a warning.
+                            if render_segment_clip(text, tag, canvas, start).is_break() {
+                                canvas.report(DiagnosticKind::Truncated);
+                            }
                         }
 
                         Overflow::GraphemeWrap => {
@@ -85,9 +144,8 @@ impl<T1: Tag + Clone, S: SegmentStore<T1> + SegmentStoreFetch<T1, T3>, T3: Tag>
 
                         Overflow::Error => {
                             if render_segment_clip(text, tag, canvas, start).is_break() {
-                                return Err(RenderableError::of(|a| {
-                                    a.handle_error(&OverflowError);
-                                }));
+                                return Err(OverflowError::at(start, canvas.get_position(), i)
+                                    .into_renderable_error());
                             }
                         }
                     }
@@ -108,27 +166,41 @@ impl<T1: Tag + Clone + Default, S: SegmentStore<T1> + SegmentStoreFetch<T1, T1>>
     ) -> Result<(), RenderableError> {
         let start = canvas.get_position();
 
+        // The tag of the last segment, which the components after it are written within.
+        let mut parent: Option<&T1> = None;
+
         for i in 0..self.segments.len() {
             let segment = match self.segments.get(i) {
                 Some(s) => s,
                 None => continue,
             };
 
+            if let TextSegment::Segment(_, tag) = segment {
+                parent = Some(tag);
+            }
+
             match segment {
                 TextSegment::Renderable(r) => {
-                    // Delegate to nested renderable as before.
-                    canvas.write(r)?;
+                    // Delegate to nested renderable, within the segment before it.
+                    match parent {
+                        Some(tag) => canvas.write_within(tag.clone(), r)?,
+                        None => canvas.write(r)?,
+                    };
                 }
                 TextSegment::Segment(text, tag) => {
                     match &self.overflow_behavior {
                         Overflow::Clip => {
-                            // we don't care when it overflows
-                            let _ = render_segment_clip(
+                            // Clipping is expected, but still worth a warning.
+                            if render_segment_clip(
                                 text,
                                 tag,
                                 &mut FlattenCanvas::new(canvas),
                                 start,
-                            );
+                            )
+                            .is_break()
+                            {
+                                canvas.report(DiagnosticKind::Truncated);
+                            }
                         }
 
                         Overflow::GraphemeWrap => {
@@ -168,9 +240,8 @@ impl<T1: Tag + Clone + Default, S: SegmentStore<T1> + SegmentStoreFetch<T1, T1>>
                             )
                             .is_break()
                             {
-                                return Err(RenderableError::of(|a| {
-                                    a.handle_error(&OverflowError);
-                                }));
+                                return Err(OverflowError::at(start, canvas.get_position(), i)
+                                    .into_renderable_error());
                             }
                         }
                     }
@@ -192,41 +263,44 @@ impl<'a, Tt: Tag> FlattenCanvas<'a, Tt> {
     }
 }
 
+fn flatten<Ot: Tag + Clone + Default>(tag: TextTag<Ot, Ot>) -> Ot {
+    match tag {
+        TextTag::Component(c) => c,
+        TextTag::Segment(s) => s,
+        TextTag::Ellipsis(_) => Default::default(),
+    }
+}
+
 impl<'a, Ot: Tag + Clone + Default> Canvas<TextTag<Ot, Ot>> for FlattenCanvas<'a, Ot> {
     fn write<'b>(
         &'b mut self,
         renderable: &dyn Renderable<TextTag<Ot, Ot>>,
     ) -> Result<CanvasSummary, RenderableError> {
-        let start = self.inner.get_position();
-        renderable.render_into(self)?;
-        let end = self.inner.get_position();
+        self.inner
+            .write(&AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut FlattenCanvas::new(canvas))
+            }))
+    }
 
-        Ok(CanvasSummary {
-            start_position: start,
-            end_position: end,
-        })
+    fn write_within(
+        &mut self,
+        parent: TextTag<Ot, Ot>,
+        renderable: &dyn Renderable<TextTag<Ot, Ot>>,
+    ) -> Result<CanvasSummary, RenderableError> {
+        self.inner.write_within(
+            flatten(parent),
+            &AdaptedRenderable::new(renderable, |canvas, renderable| {
+                renderable.render_into(&mut FlattenCanvas::new(canvas))
+            }),
+        )
     }
 
     fn set_char(&mut self, ch: char, tag: TextTag<Ot, Ot>) -> bool {
-        self.inner.set_char(
-            ch,
-            match tag {
-                TextTag::Component(c) => c,
-                TextTag::Segment(s) => s,
-                TextTag::Ellipsis(_) => Default::default(),
-            },
-        )
+        self.inner.set_char(ch, flatten(tag))
     }
 
     fn set_gph(&mut self, v: &gph, tag: TextTag<Ot, Ot>) -> bool {
-        self.inner.set_gph(
-            v,
-            match tag {
-                TextTag::Component(c) => c,
-                TextTag::Segment(s) => s,
-                TextTag::Ellipsis(_) => Default::default(),
-            },
-        )
+        self.inner.set_gph(v, flatten(tag))
     }
 
     fn can_set_gph(&mut self, v: &gph) -> bool {
@@ -234,6 +308,39 @@ impl<'a, Ot: Tag + Clone + Default> Canvas<TextTag<Ot, Ot>> for FlattenCanvas<'a
         self.inner.can_set_gph(v)
     }
 
+    fn get_gph(&self, position: RenderPosition) -> Option<&gph> {
+        self.inner.get_gph(position)
+    }
+
+    fn available_width(&self) -> Option<usize> {
+        self.inner.available_width()
+    }
+
+    fn remaining_columns(&self) -> Option<usize> {
+        self.inner.remaining_columns()
+    }
+
+    fn tab_stops(&self) -> TabStops {
+        self.inner.tab_stops()
+    }
+
+    fn set_tab_stops(&mut self, tab_stops: TabStops) -> &mut dyn Canvas<TextTag<Ot, Ot>> {
+        self.inner.set_tab_stops(tab_stops);
+        self
+    }
+
+    fn get_environment(&self, key: TypeId) -> Option<&dyn Any> {
+        self.inner.get_environment(key)
+    }
+
+    fn report(&mut self, kind: DiagnosticKind) {
+        self.inner.report(kind);
+    }
+
+    fn width_provider(&self) -> &dyn WidthProvider {
+        self.inner.width_provider()
+    }
+
     fn get_start_position(&self) -> RenderPosition {
         self.inner.get_start_position()
     }
@@ -257,6 +364,11 @@ impl<'a, Ot: Tag + Clone + Default> Canvas<TextTag<Ot, Ot>> for FlattenCanvas<'a
         self
     }
 
+    fn set_line_watermark(&mut self, line: usize) -> &mut dyn Canvas<TextTag<Ot, Ot>> {
+        self.inner.set_line_watermark(line);
+        self
+    }
+
     fn cursor_down(&mut self) -> &mut dyn Canvas<TextTag<Ot, Ot>> {
         self.inner.cursor_down();
         self
@@ -313,3 +425,52 @@ impl<'a, Ot: Tag + Clone + Default> Canvas<TextTag<Ot, Ot>> for FlattenCanvas<'a
         self
     }
 }
+
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use alloc::string::String;
+
+    use inkless_core::{
+        buffer::{RenderDispatcher, control::ControlPolicy, streaming::StreamingRenderBuffer},
+        builtins::sinks::{line_format::LineFormat, plaintext::Plaintext},
+        canvas::AmbiguityPolicy,
+        tag::untagged::Untagged,
+    };
+
+    use super::*;
+    use crate::text::WithTagged;
+
+    /// Writes a wrapped `Text`, then a column beside its first line.
+    struct Beside;
+
+    impl Renderable<Untagged> for Beside {
+        fn render_into<'buffer_reference>(
+            &self,
+            canvas: &mut dyn Canvas<Untagged>,
+        ) -> Result<(), RenderableError> {
+            let text = Text::empty::<Untagged, Untagged>()
+                .with::<Untagged>("aaa bbb ccc")
+                .word_wrap();
+            canvas.write(&text)?;
+
+            canvas.set_position(RenderPosition::new(0, 5));
+            canvas.set_char('|', Untagged);
+
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn streaming_keeps_writes_beside_wrapped_text() {
+        let (output, _) = StreamingRenderBuffer::<Untagged, 48, 4>::render_with_diagnostics(
+            Plaintext::from(String::new())
+                .with_line_format(LineFormat::default().with_trim_trailing_gaps(true)),
+            Beside,
+            6,
+            &AmbiguityPolicy::Standard,
+            ControlPolicy::default(),
+        );
+
+        assert_eq!(output.unwrap(), "aaa  |\nbbb \nccc\n");
+    }
+}
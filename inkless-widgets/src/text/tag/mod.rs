@@ -1,4 +1,4 @@
-use inkless_core::tag::Tag;
+use inkless_core::tag::{NamedTag, Tag};
 
 use crate::text::overflow::EllipsisPosition;
 
@@ -9,7 +9,27 @@ pub enum TextTag<T1: Tag + Clone, T2: Tag> {
     Ellipsis(EllipsisPosition),
 }
 
-impl<T1: Tag + Clone, T2: Tag> Tag for TextTag<T1, T2> {}
+impl<T1: Tag + Clone, T2: Tag> Tag for TextTag<T1, T2> {
+    /// Segments merge with segments and components with components; anything else keeps `self`.
+    fn merge(self, parent: &Self) -> Self {
+        match (self, parent) {
+            (Self::Segment(tag), Self::Segment(parent)) => Self::Segment(tag.merge(parent)),
+            (Self::Component(tag), Self::Component(parent)) => Self::Component(tag.merge(parent)),
+            (tag, _) => tag,
+        }
+    }
+}
+
+/// Segments and components keep their own names; the ellipsis is `text.ellipsis`.
+impl<T1: NamedTag + Clone, T2: NamedTag> NamedTag for TextTag<T1, T2> {
+    fn name(&self) -> &str {
+        match self {
+            Self::Segment(tag) => tag.name(),
+            Self::Component(tag) => tag.name(),
+            Self::Ellipsis(_) => "text.ellipsis",
+        }
+    }
+}
 
 impl<T1: Tag + Clone, T2: Tag> From<T2> for TextTag<T1, T2> {
     fn from(value: T2) -> Self {